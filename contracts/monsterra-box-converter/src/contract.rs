@@ -6,7 +6,8 @@ use cw2::set_contract_version;
 use crate::error::ContractError;
 use crate::execute::convert;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{set_signer, set_admin, get_signer, set_box_contract, is_admin, is_box_contract};
+use crate::state::{set_signer, set_admin, get_signer, set_box_contract, is_admin, is_box_contract, SIGNATURE_SCHEME, SIGNER};
+use monsterra_signing::{get_signature_scheme, set_signature_scheme};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:monsterra-box-converter";
@@ -51,6 +52,11 @@ pub fn execute(
         ExecuteMsg::Convert { msg, signature } => convert(deps, _env, info, msg, signature),
         ExecuteMsg::SetAdmin { user, status } => set_admin(deps.storage, &info, user, status),
         ExecuteMsg::SetSigner { public_key } => set_signer(deps.storage, &info, public_key),
+        ExecuteMsg::SetSignatureScheme { scheme } => {
+            let admin = is_admin(deps.storage, info.sender);
+            let signer = SIGNER.may_load(deps.storage)?;
+            Ok(set_signature_scheme(deps.storage, &SIGNATURE_SCHEME, admin, scheme, signer.as_deref())?)
+        }
         ExecuteMsg::SetBoxContract { box_contract, status } => set_box_contract(deps.storage, &info, box_contract, status),
     }
 }
@@ -61,6 +67,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::IsAdmin { user } => to_binary(&is_admin(deps.storage, user)),
         QueryMsg::GetSigner {} => to_binary(&get_signer(deps.storage)),
+        QueryMsg::GetSignatureScheme {} => to_binary(&get_signature_scheme(deps.storage, &SIGNATURE_SCHEME)),
         QueryMsg::IsBoxContract { box_contract } => to_binary(&is_box_contract(deps.storage, box_contract)),
     }
 }
@@ -1,12 +1,12 @@
 use crate::msg::{ConvertMsg, ConvertPayload, MintMsg, MonsterraNFTMsg};
-use crate::state::{get_signer, is_box_contract};
+use crate::state::{get_signer, is_box_contract, SIGNATURE_SCHEME};
 use crate::ContractError;
 use cosmwasm_std::{
     to_binary, Addr, Binary, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, Response,
     StdResult, WasmMsg, WasmQuery,
 };
 use cw721::{Cw721ExecuteMsg, Cw721QueryMsg};
-use sha2::{Digest, Sha256};
+use monsterra_signing::{get_signature_scheme, verify_signature};
 
 pub fn convert(
     deps: DepsMut,
@@ -171,10 +171,7 @@ fn verify_sig(deps: &DepsMut, convert_payload: &ConvertPayload, signature: Binar
         Err(_) => return false,
     };
     let signer = get_signer(deps.storage);
+    let scheme = get_signature_scheme(deps.storage, &SIGNATURE_SCHEME);
 
-    let hash = Sha256::digest(msg);
-    let result = deps
-        .api
-        .secp256k1_verify(hash.as_ref(), &signature, &signer.0);
-    result.unwrap_or(false)
+    verify_signature(deps.api, &scheme, &msg, &signature, &signer)
 }
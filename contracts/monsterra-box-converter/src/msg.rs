@@ -1,5 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Binary, Timestamp, Addr};
+
+use crate::state::SignatureScheme;
 #[cw_serde]
 pub struct ConvertMsg {
     pub boxes: Vec<NftInfo>,
@@ -58,6 +60,10 @@ pub enum ExecuteMsg {
         public_key: Binary,
     },
 
+    SetSignatureScheme {
+        scheme: SignatureScheme,
+    },
+
     SetBoxContract {
         box_contract: Addr,
         status: bool,
@@ -78,6 +84,9 @@ pub enum QueryMsg {
     #[returns(Binary)]
     GetSigner {},
 
+    #[returns(SignatureScheme)]
+    GetSignatureScheme {},
+
     #[returns(bool)]
     IsBoxContract { box_contract: Addr },
 }
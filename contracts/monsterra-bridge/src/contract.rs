@@ -1,21 +1,42 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    Storage, Uint128, WasmMsg,
+    to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::Cw20ExecuteMsg;
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw721_base::{ExecuteMsg as Cw721ExecuteMsg, Extension};
+use monsterra_signing::{get_signature_scheme, set_signature_scheme, verify_signature};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SwapMessage};
+use crate::msg::{
+    ConfigResponse, DestinationChainsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    NftSwapMessage, PauseStateResponse, QueryMsg, SignedPayload, SignerResponse, SignersResponse,
+    SwapMessage, SwapsResponse, TokenConfigResponse, TokenConfigsResponse, TokenRoutesResponse,
+    WindowUsageResponse,
+};
+use crate::signing::{mint_signing_bytes, nft_mint_signing_bytes, refund_signing_bytes};
 use crate::state::{
-    get_max_swap_amount, get_owner, get_signer, get_swap_data, is_accepted_des_token,
-    is_accepted_token, is_admin, is_approve_transaction, is_operator, set_accepted_des_token,
-    set_accepted_token, set_admin, set_approve_transaction, set_max_swap_amount, set_new_owner,
-    set_operator, set_signer, set_swap_data, Swapdata, OWNER,
+    add_collected_fees, add_signer, decrease_locked_balance, get_accepted_tokens, get_bridge_fee,
+    get_collected_fees, get_destination_chain, get_destination_chains, get_locked_balance,
+    get_max_swap_amount, get_owner, get_paused_tokens, get_pending_mint, get_processed_status,
+    get_signer, get_signers, get_swap_data, get_threshold, get_token_mode, get_token_route,
+    get_token_routes, get_volume_limit, get_window_usage, increase_locked_balance,
+    is_accepted_collection, is_accepted_des_collection, is_accepted_des_token, is_accepted_token,
+    is_admin, is_approve_transaction, is_direction_paused, is_globally_paused, is_nonce_used,
+    is_operator, is_paused, is_within_volume_limit, list_swap_datas, migrate_legacy_signer,
+    migrate_legacy_swap_datas, record_mint_volume, remove_signer, set_accepted_collection,
+    set_accepted_des_collection, set_accepted_des_token, set_accepted_token, set_admin,
+    set_approve_transaction, set_bridge_fee, set_destination_chain, set_max_swap_amount,
+    set_new_owner, set_nonce_used, set_operator, set_paused, set_pending_mint,
+    set_processed_status, set_swap_data, set_threshold, set_token_mode, set_token_route,
+    set_volume_limit, withdraw_collected_fees, AddressFormat, BridgeFee, DestinationChain,
+    Direction, FeeAsset, PauseTarget, PendingMint, PendingMintStatus, ProcessedStatus,
+    SignatureScheme, Swapdata, TokenMode, OWNER, SIGNATURE_SCHEME, SIGNERS,
 };
-use sha2::{Digest, Sha256};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:monstera-bridge";
@@ -23,8 +44,36 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 //allow contract migration
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {});
+    }
+
+    let stored_version =
+        Version::parse(&stored.version).map_err(|_| ContractError::InvalidContractVersion {})?;
+    let current_version =
+        Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidContractVersion {})?;
+    if stored_version > current_version {
+        return Err(ContractError::CannotDowngrade {});
+    }
+
+    // 0.2.0 moved to the signer set, indexed swap data and the processed registry
+    if stored_version < Version::new(0, 2, 0) {
+        migrate_legacy_signer(deps.storage)?;
+        migrate_legacy_swap_datas(
+            deps.storage,
+            &env.block.chain_id,
+            msg.legacy_source_chain.as_deref(),
+        )?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -47,7 +96,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -70,12 +119,71 @@ pub fn execute(
             transaction_id,
             status,
         } => try_set_approve_transaction(deps.storage, info, transaction_id, status),
-        ExecuteMsg::SetSigner { public_key } => try_set_signer(deps.storage, info, public_key),
+        ExecuteMsg::AddSigner { public_key } => try_add_signer(deps.storage, info, public_key),
+        ExecuteMsg::RemoveSigner { index } => try_remove_signer(deps.storage, info, index),
+        ExecuteMsg::SetThreshold { threshold } => try_set_threshold(deps.storage, info, threshold),
+        ExecuteMsg::SetSignatureScheme { scheme } => {
+            try_set_signature_scheme(deps.storage, info, scheme)
+        }
         ExecuteMsg::Mint {
             swap_message,
-            signature,
-        } => try_mint(deps, info, swap_message, signature),
-        ExecuteMsg::Burn { swap_message } => try_burn(deps.storage, info, swap_message),
+            payload,
+            signatures,
+        } => try_mint(deps, env, info, swap_message, payload, signatures),
+        ExecuteMsg::Burn { swap_message } => try_burn(deps.storage, env, info, swap_message),
+        ExecuteMsg::SetVolumeLimit {
+            token,
+            max_amount,
+            window,
+        } => try_set_volume_limit(deps.storage, info, token, max_amount, window),
+        ExecuteMsg::ReleasePendingMint {
+            source_chain,
+            transaction_id,
+        } => try_release_pending_mint(deps.storage, env, info, source_chain, transaction_id),
+        ExecuteMsg::RejectPendingMint {
+            source_chain,
+            transaction_id,
+        } => try_reject_pending_mint(deps.storage, info, source_chain, transaction_id),
+        ExecuteMsg::SetTokenMode { token, mode } => {
+            try_set_token_mode(deps.storage, info, token, mode)
+        }
+        ExecuteMsg::SetAcceptedCollection { collection, status } => {
+            try_set_accepted_collection(deps.storage, info, collection, status)
+        }
+        ExecuteMsg::SetAcceptedDesCollection { collection, status } => {
+            try_set_accepted_des_collection(deps.storage, info, collection, status)
+        }
+        ExecuteMsg::MintNft {
+            nft_message,
+            payload,
+            signatures,
+        } => try_mint_nft(deps, env, info, nft_message, payload, signatures),
+        ExecuteMsg::BurnNft { nft_message } => try_burn_nft(deps, env, info, nft_message),
+        ExecuteMsg::SetBridgeFee { des_token, fee } => {
+            try_set_bridge_fee(deps.storage, info, des_token, fee)
+        }
+        ExecuteMsg::WithdrawFees {
+            asset,
+            recipient,
+            amount,
+        } => try_withdraw_fees(deps, info, asset, recipient, amount),
+        ExecuteMsg::RefundBurn {
+            transaction_id,
+            expiry,
+            signatures,
+        } => try_refund_burn(deps, env, info, transaction_id, expiry, signatures),
+        ExecuteMsg::SetDestinationChain {
+            chain_id,
+            address_format,
+            enabled,
+        } => try_set_destination_chain(deps.storage, info, chain_id, address_format, enabled),
+        ExecuteMsg::SetTokenRoute {
+            cur_token,
+            chain_id,
+            des_token,
+        } => try_set_token_route(deps.storage, info, cur_token, chain_id, des_token),
+        ExecuteMsg::Pause { target } => try_set_paused(deps.storage, info, target, true),
+        ExecuteMsg::Unpause { target } => try_set_paused(deps.storage, info, target, false),
     }
 }
 
@@ -141,22 +249,175 @@ pub fn try_set_approve_transaction(
     set_approve_transaction(storage, &info, transaction_id, status)
 }
 
-pub fn try_set_signer(
+pub fn try_add_signer(
     storage: &mut dyn Storage,
     info: MessageInfo,
     public_key: Binary,
 ) -> Result<Response, ContractError> {
-    set_signer(storage, &info, public_key)
+    add_signer(storage, &info, public_key)
+}
+
+pub fn try_remove_signer(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    index: u32,
+) -> Result<Response, ContractError> {
+    remove_signer(storage, &info, index)
+}
+
+pub fn try_set_threshold(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    set_threshold(storage, &info, threshold)
+}
+
+pub fn try_set_signature_scheme(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    scheme: SignatureScheme,
+) -> Result<Response, ContractError> {
+    let admin = is_admin(storage, info.sender);
+    let signers = get_signers(storage);
+    Ok(set_signature_scheme(
+        storage,
+        &SIGNATURE_SCHEME,
+        admin,
+        scheme,
+        signers.iter().map(|(_, key)| key.as_slice()),
+    )?)
+}
+
+pub fn try_set_volume_limit(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    token: String,
+    max_amount: Uint128,
+    window: u64,
+) -> Result<Response, ContractError> {
+    set_volume_limit(storage, &info, token, max_amount, window)
+}
+
+pub fn try_set_token_mode(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    token: String,
+    mode: TokenMode,
+) -> Result<Response, ContractError> {
+    set_token_mode(storage, &info, token, mode)
+}
+
+pub fn try_set_accepted_collection(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    collection: String,
+    status: bool,
+) -> Result<Response, ContractError> {
+    set_accepted_collection(storage, &info, collection, status)
+}
+
+pub fn try_set_accepted_des_collection(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    collection: String,
+    status: bool,
+) -> Result<Response, ContractError> {
+    set_accepted_des_collection(storage, &info, collection, status)
+}
+
+pub fn try_set_bridge_fee(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    des_token: String,
+    fee: Option<BridgeFee>,
+) -> Result<Response, ContractError> {
+    set_bridge_fee(storage, &info, des_token, fee)
+}
+
+pub fn try_withdraw_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: FeeAsset,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    deps.api.addr_validate(&recipient)?;
+    withdraw_collected_fees(deps.storage, &info, &asset, amount)?;
+
+    let msg: CosmosMsg = match &asset {
+        FeeAsset::Cw20 { address } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: address.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.clone(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+        FeeAsset::Native { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.clone(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }),
+    };
+
+    Ok(Response::new().add_message(msg).add_attributes(vec![
+        ("method", "withdraw_fees"),
+        ("asset", &asset.key()),
+        ("recipient", &recipient),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+pub fn try_set_paused(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    target: PauseTarget,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    set_paused(storage, &info, target, paused)
+}
+
+pub fn try_set_destination_chain(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    chain_id: String,
+    address_format: AddressFormat,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    set_destination_chain(storage, &info, chain_id, address_format, enabled)
+}
+
+pub fn try_set_token_route(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    cur_token: String,
+    chain_id: String,
+    des_token: Option<String>,
+) -> Result<Response, ContractError> {
+    set_token_route(storage, &info, cur_token, chain_id, des_token)
 }
 
 pub fn try_mint(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     swap_message: SwapMessage,
-    signature: Binary,
+    payload: SignedPayload,
+    signatures: Vec<(u32, Binary)>,
 ) -> Result<Response, ContractError> {
     //check requirements
-    if is_transaction_existed(deps.storage, swap_message.transaction_id.clone()) {
+    if is_paused(deps.storage, &swap_message.cur_token, &Direction::Mint) {
+        return Err(ContractError::Paused {});
+    }
+
+    if is_transaction_existed(
+        deps.storage,
+        &payload.source_chain,
+        &swap_message.transaction_id,
+    ) {
         return Err(ContractError::TransactionExisted {});
     }
 
@@ -164,31 +425,196 @@ pub fn try_mint(
         return Err(ContractError::InvalidSwapData {});
     }
 
+    // the counterpart of a mint is the chain it was sent from
+    if swap_message.des_chain != payload.source_chain {
+        return Err(ContractError::InvalidSwapData {});
+    }
+    _validate_route(deps.storage, &swap_message)?;
+
     if !_validate_max_swap_amount(deps.storage, &swap_message) {
         return Err(ContractError::ExceededMaxAmount {});
     }
 
-    if !is_valid_swap_message(deps.as_ref(), &swap_message, signature) {
+    let now = env.block.time.seconds();
+    if now > payload.expiry {
+        return Err(ContractError::SignatureExpired {});
+    }
+
+    let msg = mint_signing_bytes(
+        &env.block.chain_id,
+        env.contract.address.as_str(),
+        &payload,
+        &swap_message,
+    );
+    if !is_valid_swap_message(deps.as_ref(), &msg, &signatures) {
         return Err(ContractError::InvalidSignature {});
     }
+    if is_nonce_used(deps.storage, &payload.source_chain, payload.nonce) {
+        return Err(ContractError::NonceUsed {});
+    }
+    set_nonce_used(deps.storage, &payload.source_chain, payload.nonce)?;
+
+    //hold the mint for operators when it would exceed the token's window volume
+    if !is_within_volume_limit(
+        deps.storage,
+        &swap_message.cur_token,
+        swap_message.amount,
+        now,
+    ) {
+        set_processed_status(
+            deps.storage,
+            &payload.source_chain,
+            &swap_message.transaction_id,
+            ProcessedStatus::Pending,
+        )?;
+        let pending_mint = PendingMint {
+            swap_message: swap_message.clone(),
+            source_chain: payload.source_chain.clone(),
+            queued_at: now,
+            status: PendingMintStatus::Pending,
+        };
+        set_pending_mint(
+            deps.storage,
+            &payload.source_chain,
+            &swap_message.transaction_id,
+            &pending_mint,
+        )?;
+
+        return Ok(Response::new().add_attributes(vec![
+            ("method", "mint"),
+            ("transaction_id", &swap_message.transaction_id),
+            ("cur_token", &swap_message.cur_token),
+            ("cur_user", &swap_message.cur_user),
+            ("swap_amount", &swap_message.amount.to_string()),
+            ("status", "pending"),
+        ]));
+    }
+
+    execute_mint(
+        deps.storage,
+        &swap_message,
+        &payload.source_chain,
+        &env.block,
+        "mint",
+    )
+}
+
+pub fn try_release_pending_mint(
+    storage: &mut dyn Storage,
+    env: Env,
+    info: MessageInfo,
+    source_chain: String,
+    transaction_id: String,
+) -> Result<Response, ContractError> {
+    if !is_operator(storage, info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pending_mint = match get_pending_mint(storage, &source_chain, &transaction_id) {
+        Some(value) if value.status == PendingMintStatus::Pending => value,
+        _ => return Err(ContractError::PendingMintNotFound {}),
+    };
+    if is_paused(
+        storage,
+        &pending_mint.swap_message.cur_token,
+        &Direction::Mint,
+    ) {
+        return Err(ContractError::Paused {});
+    }
+    pending_mint.status = PendingMintStatus::Released;
+    set_pending_mint(storage, &source_chain, &transaction_id, &pending_mint)?;
+
+    execute_mint(
+        storage,
+        &pending_mint.swap_message,
+        &pending_mint.source_chain,
+        &env.block,
+        "release_pending_mint",
+    )
+}
+
+pub fn try_reject_pending_mint(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    source_chain: String,
+    transaction_id: String,
+) -> Result<Response, ContractError> {
+    if !is_operator(storage, info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pending_mint = match get_pending_mint(storage, &source_chain, &transaction_id) {
+        Some(value) if value.status == PendingMintStatus::Pending => value,
+        _ => return Err(ContractError::PendingMintNotFound {}),
+    };
+    pending_mint.status = PendingMintStatus::Rejected;
+    set_pending_mint(storage, &source_chain, &transaction_id, &pending_mint)?;
+    // the source chain refunds the sender, so the transaction can never be minted
+    set_processed_status(
+        storage,
+        &pending_mint.source_chain,
+        &transaction_id,
+        ProcessedStatus::Refunded,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "reject_pending_mint")
+        .add_attribute("transaction_id", transaction_id))
+}
+
+// Records the swap and its window volume, then mints to the receiver
+fn execute_mint(
+    storage: &mut dyn Storage,
+    swap_message: &SwapMessage,
+    source_chain: &str,
+    block: &BlockInfo,
+    method: &str,
+) -> Result<Response, ContractError> {
+    set_processed_status(
+        storage,
+        source_chain,
+        &swap_message.transaction_id,
+        ProcessedStatus::Completed,
+    )?;
 
     //save swap data
-    let swap_data = Swapdata::default(&swap_message, String::from("mint"));
-    set_swap_data(deps.storage, swap_message.transaction_id.clone(), swap_data)?;
+    let swap_data = Swapdata::default(swap_message, String::from("mint"), block);
+    set_swap_data(
+        storage,
+        source_chain,
+        &swap_message.transaction_id,
+        swap_data,
+    )?;
+    record_mint_volume(
+        storage,
+        &swap_message.cur_token,
+        swap_message.amount,
+        block.time.seconds(),
+    )?;
 
-    //mint new tokens for corresponding accounts
+    //mint new tokens, or release escrowed ones, for corresponding accounts
+    let msg = match get_token_mode(storage, &swap_message.cur_token) {
+        TokenMode::MintBurn => Cw20ExecuteMsg::Mint {
+            recipient: swap_message.cur_user.clone(),
+            amount: swap_message.amount,
+        },
+        TokenMode::LockRelease => {
+            decrease_locked_balance(storage, &swap_message.cur_token, swap_message.amount)?;
+            Cw20ExecuteMsg::Transfer {
+                recipient: swap_message.cur_user.clone(),
+                amount: swap_message.amount,
+            }
+        }
+    };
     let mut messages: Vec<CosmosMsg> = vec![];
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: swap_message.cur_token.clone(),
-        msg: to_binary(&Cw20ExecuteMsg::Mint {
-            recipient: swap_message.cur_user.clone(),
-            amount: swap_message.amount.clone(),
-        })?,
+        msg: to_binary(&msg)?,
         funds: vec![],
     }));
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("method", "mint"),
+        ("method", method),
         ("transaction_id", &swap_message.transaction_id),
         ("cur_token", &swap_message.cur_token),
         ("des_token", &swap_message.des_token),
@@ -201,29 +627,109 @@ pub fn try_mint(
 
 pub fn try_burn(
     storage: &mut dyn Storage,
+    env: Env,
     info: MessageInfo,
     swap_message: SwapMessage,
 ) -> Result<Response, ContractError> {
-    if is_transaction_existed(storage, swap_message.transaction_id.clone()) {
+    if is_paused(storage, &swap_message.cur_token, &Direction::Burn) {
+        return Err(ContractError::Paused {});
+    }
+
+    if is_transaction_existed(storage, &env.block.chain_id, &swap_message.transaction_id) {
         return Err(ContractError::TransactionExisted {});
     }
 
     if !_validate_swap_data(storage, info.sender, &swap_message) {
         return Err(ContractError::InvalidSwapData {});
     }
-    //save swap data
-    let swap_data = Swapdata::default(&swap_message, String::from("burn"));
-    set_swap_data(storage, swap_message.transaction_id.clone(), swap_data)?;
+    _validate_route(storage, &swap_message)?;
 
-    //burn tokens of corresponding accounts
+    //take the des_token fee, either out of the bridged amount or from the attached coins
+    let fee = get_bridge_fee(storage, &swap_message.des_token);
+    let fee_amount = match &fee {
+        Some(fee) => fee.compute(swap_message.amount)?,
+        None => Uint128::zero(),
+    };
+    let (fee_asset, token_fee) = match fee.and_then(|fee| fee.native_denom) {
+        Some(denom) => {
+            let paid = match info.funds.as_slice() {
+                [] => Uint128::zero(),
+                [coin] if coin.denom == denom => coin.amount,
+                _ => return Err(ContractError::InvalidFeePayment {}),
+            };
+            if paid != fee_amount {
+                return Err(ContractError::InvalidFeePayment {});
+            }
+            (FeeAsset::Native { denom }, Uint128::zero())
+        }
+        None => {
+            // nothing but a native fee may be attached, it would be stuck in the bridge
+            if !info.funds.is_empty() {
+                return Err(ContractError::InvalidFeePayment {});
+            }
+            (
+                FeeAsset::Cw20 {
+                    address: swap_message.cur_token.clone(),
+                },
+                fee_amount,
+            )
+        }
+    };
+    if token_fee >= swap_message.amount {
+        return Err(ContractError::FeeExceedsAmount {});
+    }
+    let net_amount = swap_message.amount - token_fee;
+    if !fee_amount.is_zero() {
+        add_collected_fees(storage, &fee_asset, fee_amount)?;
+    }
+
+    //save swap data, the relayer mints the net amount on the destination chain
+    set_processed_status(
+        storage,
+        &env.block.chain_id,
+        &swap_message.transaction_id,
+        ProcessedStatus::Completed,
+    )?;
+    let mut swap_data = Swapdata::default(&swap_message, String::from("burn"), &env.block);
+    swap_data.amount = net_amount;
+    set_swap_data(
+        storage,
+        &env.block.chain_id,
+        &swap_message.transaction_id,
+        swap_data,
+    )?;
+
+    //burn tokens of corresponding accounts, or escrow them in the bridge
     let mut messages: Vec<CosmosMsg> = vec![];
+    if !token_fee.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: swap_message.cur_token.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: swap_message.cur_user.clone(),
+                recipient: env.contract.address.to_string(),
+                amount: token_fee,
+            })?,
+            funds: vec![],
+        }));
+    }
 
+    let msg = match get_token_mode(storage, &swap_message.cur_token) {
+        TokenMode::MintBurn => Cw20ExecuteMsg::BurnFrom {
+            owner: swap_message.cur_user.clone(),
+            amount: net_amount,
+        },
+        TokenMode::LockRelease => {
+            increase_locked_balance(storage, &swap_message.cur_token, net_amount)?;
+            Cw20ExecuteMsg::TransferFrom {
+                owner: swap_message.cur_user.clone(),
+                recipient: env.contract.address.to_string(),
+                amount: net_amount,
+            }
+        }
+    };
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: swap_message.cur_token.clone(),
-        msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
-            owner: swap_message.cur_user.clone(),
-            amount: swap_message.amount.clone(),
-        })?,
+        msg: to_binary(&msg)?,
         funds: vec![],
     }));
 
@@ -234,19 +740,263 @@ pub fn try_burn(
         ("des_token", &swap_message.des_token),
         ("cur_user", &swap_message.cur_user),
         ("des_user", &swap_message.des_user),
-        ("swap_amount", &swap_message.amount.to_string()),
+        ("swap_amount", &net_amount.to_string()),
+        ("fee", &fee_amount.to_string()),
+        ("fee_asset", &fee_asset.key()),
         ("side", "burn"),
     ]))
 }
 
-fn is_transaction_existed(storage: &mut dyn Storage, transaction_id: String) -> bool {
-    let data = get_swap_data(storage, transaction_id);
+// Returns a burn that the destination chain never honoured to its sender
+pub fn try_refund_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transaction_id: String,
+    expiry: u64,
+    signatures: Vec<(u32, Binary)>,
+) -> Result<Response, ContractError> {
+    if !is_operator(deps.storage, info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let chain_id = env.block.chain_id.clone();
+    let mut swap_data = match get_swap_data(deps.storage, &chain_id, &transaction_id) {
+        Some(value) if value.side == "burn" => value,
+        _ => return Err(ContractError::SwapNotFound {}),
+    };
+    if swap_data.refunded
+        || get_processed_status(deps.storage, &chain_id, &transaction_id)
+            == Some(ProcessedStatus::Refunded)
+    {
+        return Err(ContractError::AlreadyRefunded {});
+    }
+
+    if is_paused(deps.storage, &swap_data.cur_token, &Direction::Mint) {
+        return Err(ContractError::Paused {});
+    }
+
+    if env.block.time.seconds() > expiry {
+        return Err(ContractError::SignatureExpired {});
+    }
+
+    let msg = refund_signing_bytes(
+        &chain_id,
+        env.contract.address.as_str(),
+        &transaction_id,
+        expiry,
+    );
+    if !is_valid_swap_message(deps.as_ref(), &msg, &signatures) {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    swap_data.refunded = true;
+    set_swap_data(deps.storage, &chain_id, &transaction_id, swap_data.clone())?;
+    set_processed_status(
+        deps.storage,
+        &chain_id,
+        &transaction_id,
+        ProcessedStatus::Refunded,
+    )?;
+
+    //re-mint the burnt tokens, or release the escrowed ones, to the sender
+    let msg = match get_token_mode(deps.storage, &swap_data.cur_token) {
+        TokenMode::MintBurn => Cw20ExecuteMsg::Mint {
+            recipient: swap_data.cur_user.clone(),
+            amount: swap_data.amount,
+        },
+        TokenMode::LockRelease => {
+            decrease_locked_balance(deps.storage, &swap_data.cur_token, swap_data.amount)?;
+            Cw20ExecuteMsg::Transfer {
+                recipient: swap_data.cur_user.clone(),
+                amount: swap_data.amount,
+            }
+        }
+    };
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: swap_data.cur_token.clone(),
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("method", "refund_burn"),
+            ("transaction_id", &transaction_id),
+            ("cur_token", &swap_data.cur_token),
+            ("cur_user", &swap_data.cur_user),
+            ("swap_amount", &swap_data.amount.to_string()),
+        ]))
+}
+
+pub fn try_mint_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nft_message: NftSwapMessage,
+    payload: SignedPayload,
+    signatures: Vec<(u32, Binary)>,
+) -> Result<Response, ContractError> {
+    //check requirements
+    if is_paused(deps.storage, &nft_message.cur_collection, &Direction::Mint) {
+        return Err(ContractError::Paused {});
+    }
+
+    if is_transaction_existed(
+        deps.storage,
+        &payload.source_chain,
+        &nft_message.transaction_id,
+    ) {
+        return Err(ContractError::TransactionExisted {});
+    }
+
+    if !_validate_nft_swap_data(deps.storage, info.sender, &nft_message) {
+        return Err(ContractError::InvalidSwapData {});
+    }
+
+    // the counterpart of a mint is the chain it was sent from
+    if nft_message.des_chain != payload.source_chain {
+        return Err(ContractError::InvalidSwapData {});
+    }
+    _validate_nft_route(deps.storage, &nft_message)?;
+
+    if env.block.time.seconds() > payload.expiry {
+        return Err(ContractError::SignatureExpired {});
+    }
+
+    let msg = nft_mint_signing_bytes(
+        &env.block.chain_id,
+        env.contract.address.as_str(),
+        &payload,
+        &nft_message,
+    );
+    if !is_valid_swap_message(deps.as_ref(), &msg, &signatures) {
+        return Err(ContractError::InvalidSignature {});
+    }
+    if is_nonce_used(deps.storage, &payload.source_chain, payload.nonce) {
+        return Err(ContractError::NonceUsed {});
+    }
+    set_nonce_used(deps.storage, &payload.source_chain, payload.nonce)?;
+
+    //save swap data
+    set_processed_status(
+        deps.storage,
+        &payload.source_chain,
+        &nft_message.transaction_id,
+        ProcessedStatus::Completed,
+    )?;
+    let swap_data = Swapdata::nft(&nft_message, String::from("mint_nft"), &env.block);
+    set_swap_data(
+        deps.storage,
+        &payload.source_chain,
+        &nft_message.transaction_id,
+        swap_data,
+    )?;
+
+    //mint the bridged nft for corresponding account
+    let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_message.cur_collection.clone(),
+        msg: to_binary(&Cw721ExecuteMsg::<Extension, Empty>::Mint {
+            token_id: nft_message.token_id.clone(),
+            owner: nft_message.cur_user.clone(),
+            token_uri: nft_message.token_uri.clone(),
+            extension: None,
+        })?,
+        funds: vec![],
+    })];
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("method", "mint_nft"),
+        ("transaction_id", &nft_message.transaction_id),
+        ("cur_collection", &nft_message.cur_collection),
+        ("des_collection", &nft_message.des_collection),
+        ("cur_user", &nft_message.cur_user),
+        ("des_user", &nft_message.des_user),
+        ("token_id", &nft_message.token_id),
+        ("side", "mint_nft"),
+    ]))
+}
+
+// The bridge must be approved for the nft, the token_uri is carried in the event
+pub fn try_burn_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    nft_message: NftSwapMessage,
+) -> Result<Response, ContractError> {
+    if is_paused(deps.storage, &nft_message.cur_collection, &Direction::Burn) {
+        return Err(ContractError::Paused {});
+    }
+
+    if is_transaction_existed(
+        deps.storage,
+        &env.block.chain_id,
+        &nft_message.transaction_id,
+    ) {
+        return Err(ContractError::TransactionExisted {});
+    }
+
+    if !_validate_nft_swap_data(deps.storage, info.sender, &nft_message) {
+        return Err(ContractError::InvalidSwapData {});
+    }
+    _validate_nft_route(deps.storage, &nft_message)?;
 
-    if data.amount == Uint128::new(0) {
-        false
-    } else {
-        true
+    // an approval on someone else's token must not let the sender bridge it away
+    let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+        &nft_message.cur_collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: nft_message.token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+    if owner.owner != nft_message.cur_user {
+        return Err(ContractError::Unauthorized {});
     }
+
+    //save swap data
+    set_processed_status(
+        deps.storage,
+        &env.block.chain_id,
+        &nft_message.transaction_id,
+        ProcessedStatus::Completed,
+    )?;
+    let swap_data = Swapdata::nft(&nft_message, String::from("burn_nft"), &env.block);
+    set_swap_data(
+        deps.storage,
+        &env.block.chain_id,
+        &nft_message.transaction_id,
+        swap_data,
+    )?;
+
+    //burn the nft of corresponding account
+    let messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_message.cur_collection.clone(),
+        msg: to_binary(&Cw721ExecuteMsg::<Extension, Empty>::Burn {
+            token_id: nft_message.token_id.clone(),
+        })?,
+        funds: vec![],
+    })];
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("method", "burn_nft"),
+        ("transaction_id", &nft_message.transaction_id),
+        ("cur_collection", &nft_message.cur_collection),
+        ("des_collection", &nft_message.des_collection),
+        ("cur_user", &nft_message.cur_user),
+        ("des_user", &nft_message.des_user),
+        ("token_id", &nft_message.token_id),
+        (
+            "token_uri",
+            nft_message.token_uri.as_deref().unwrap_or_default(),
+        ),
+        ("side", "burn_nft"),
+    ]))
+}
+
+// Swap data and pending mints share the (source_chain, transaction_id) key, so every
+// record they hold is also in the processed registry
+fn is_transaction_existed(storage: &dyn Storage, source_chain: &str, transaction_id: &str) -> bool {
+    get_processed_status(storage, source_chain, transaction_id).is_some()
 }
 
 fn _validate_swap_data(
@@ -271,6 +1021,70 @@ fn _validate_swap_data(
     return true;
 }
 
+fn _validate_route(storage: &dyn Storage, swap_message: &SwapMessage) -> Result<(), ContractError> {
+    _validate_chain_route(
+        storage,
+        &swap_message.des_chain,
+        &swap_message.cur_token,
+        &swap_message.des_token,
+        &swap_message.des_user,
+    )
+}
+
+// collections are routed like tokens, through SetTokenRoute
+fn _validate_nft_route(
+    storage: &dyn Storage,
+    nft_message: &NftSwapMessage,
+) -> Result<(), ContractError> {
+    _validate_chain_route(
+        storage,
+        &nft_message.des_chain,
+        &nft_message.cur_collection,
+        &nft_message.des_collection,
+        &nft_message.des_user,
+    )
+}
+
+fn _validate_chain_route(
+    storage: &dyn Storage,
+    des_chain: &String,
+    cur_token: &String,
+    des_token: &String,
+    des_user: &str,
+) -> Result<(), ContractError> {
+    let chain = match get_destination_chain(storage, des_chain) {
+        Some(value) if value.enabled => value,
+        _ => return Err(ContractError::ChainNotEnabled {}),
+    };
+
+    if get_token_route(storage, cur_token, des_chain).as_ref() != Some(des_token) {
+        return Err(ContractError::RouteNotFound {});
+    }
+
+    if !chain.address_format.is_valid(des_user) {
+        return Err(ContractError::InvalidDesUser {});
+    }
+    Ok(())
+}
+
+fn _validate_nft_swap_data(
+    storage: &mut dyn Storage,
+    sender: Addr,
+    nft_message: &NftSwapMessage,
+) -> bool {
+    if nft_message.token_id.is_empty() {
+        return false;
+    }
+
+    if !is_accepted_collection(storage, &nft_message.cur_collection)
+        || !is_accepted_des_collection(storage, &nft_message.des_collection)
+    {
+        return false;
+    }
+
+    sender == nft_message.cur_user
+}
+
 fn _validate_max_swap_amount(storage: &mut dyn Storage, swap_message: &SwapMessage) -> bool {
     let max_amount: Uint128 = get_max_swap_amount(storage, &swap_message.cur_token);
 
@@ -281,36 +1095,60 @@ fn _validate_max_swap_amount(storage: &mut dyn Storage, swap_message: &SwapMessa
     return is_approve_transaction(storage, &swap_message.transaction_id);
 }
 
-fn is_valid_swap_message(deps: Deps, swap_message: &SwapMessage, signature: Binary) -> bool {
-    let msg: Binary;
-    let result = to_binary(swap_message);
-    match result {
-        Ok(value) => msg = value,
-        Err(_) => return false,
-    };
-    let signer = get_signer(deps.storage);
-
-    query_verify_cosmos(deps, &msg, &signature, &signer.0)
-}
+// Requires valid signatures from at least `threshold` distinct signers
+fn is_valid_swap_message(deps: Deps, msg: &[u8], signatures: &[(u32, Binary)]) -> bool {
+    let threshold = get_threshold(deps.storage);
+    if threshold == 0 {
+        return false;
+    }
 
-fn query_verify_cosmos(deps: Deps, message: &[u8], signature: &[u8], public_key: &[u8]) -> bool {
-    let hash = Sha256::digest(message);
-    let result = deps
-        .api
-        .secp256k1_verify(hash.as_ref(), signature, public_key);
-    match result {
-        Ok(value) => value,
-        Err(_) => false,
+    let scheme = get_signature_scheme(deps.storage, &SIGNATURE_SCHEME);
+    let mut verified: Vec<u32> = vec![];
+    for (index, signature) in signatures {
+        if verified.contains(index) {
+            return false;
+        }
+        let signer = match SIGNERS.may_load(deps.storage, *index) {
+            Ok(Some(value)) => value,
+            _ => return false,
+        };
+        if !verify_signature(deps.api, &scheme, msg, signature, &signer) {
+            return false;
+        }
+        verified.push(*index);
     }
+
+    verified.len() as u32 >= threshold
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetOwner {} => to_binary(&query_owner(deps.storage)),
+        QueryMsg::Config {} => to_binary(&query_config(deps.storage)?),
+        QueryMsg::TokenConfig { token } => to_binary(&query_token_config(deps.storage, token)),
+        QueryMsg::ListTokenConfigs { start_after, limit } => {
+            to_binary(&query_token_configs(deps.storage, start_after, limit))
+        }
+        QueryMsg::ListDestinationChains { start_after, limit } => {
+            to_binary(&DestinationChainsResponse {
+                chains: get_destination_chains(deps.storage, start_after, limit),
+            })
+        }
+        QueryMsg::ListTokenRoutes {
+            cur_token,
+            start_after,
+            limit,
+        } => to_binary(&TokenRoutesResponse {
+            routes: get_token_routes(deps.storage, &cur_token, start_after, limit),
+        }),
+        QueryMsg::GetOwner {} => to_binary(&query_owner(deps.storage)?),
         QueryMsg::IsAdmin { user } => to_binary(&query_admin(deps.storage, user)),
         QueryMsg::IsOperator { operator } => to_binary(&query_operator(deps.storage, operator)),
-        QueryMsg::GetSigner {} => to_binary(&query_signer(deps.storage)),
+        QueryMsg::GetSigner { index } => to_binary(&query_signer(deps.storage, index)),
+        QueryMsg::GetSigners {} => to_binary(&query_signers(deps.storage)),
+        QueryMsg::GetSignatureScheme {} => {
+            to_binary(&get_signature_scheme(deps.storage, &SIGNATURE_SCHEME))
+        }
         QueryMsg::IsAcceptedToken { token } => {
             to_binary(&query_accepted_token(deps.storage, token))
         }
@@ -323,19 +1161,109 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsApproveTransaction { transaction_id } => {
             to_binary(&query_approve_transaction(deps.storage, transaction_id))
         }
-        QueryMsg::GetSwapData { transaction_id } => {
-            to_binary(&query_swap_data(deps.storage, transaction_id))
+        QueryMsg::GetSwapData {
+            source_chain,
+            transaction_id,
+        } => to_binary(&query_swap_data(
+            deps.storage,
+            env,
+            source_chain,
+            transaction_id,
+        )),
+        QueryMsg::GetWindowUsage { token } => {
+            to_binary(&query_window_usage(deps.storage, env, token))
         }
-        QueryMsg::Test {} => to_binary(&query_test()),
+        QueryMsg::GetProcessedStatus {
+            source_chain,
+            transaction_id,
+        } => to_binary(&query_processed_status(
+            deps.storage,
+            source_chain,
+            transaction_id,
+        )),
+        QueryMsg::GetPendingMint {
+            source_chain,
+            transaction_id,
+        } => to_binary(&query_pending_mint(
+            deps.storage,
+            source_chain,
+            transaction_id,
+        )),
+        QueryMsg::GetTokenMode { token } => to_binary(&query_token_mode(deps.storage, token)),
+        QueryMsg::GetLockedBalance { token } => {
+            to_binary(&query_locked_balance(deps.storage, token))
+        }
+        QueryMsg::IsAcceptedCollection { collection } => {
+            to_binary(&query_accepted_collection(deps.storage, collection))
+        }
+        QueryMsg::IsAcceptedDesCollection { collection } => {
+            to_binary(&query_accepted_des_collection(deps.storage, collection))
+        }
+        QueryMsg::GetBridgeFee { des_token } => {
+            to_binary(&query_bridge_fee(deps.storage, des_token))
+        }
+        QueryMsg::GetCollectedFees { asset } => {
+            to_binary(&query_collected_fees(deps.storage, asset))
+        }
+        QueryMsg::GetPauseState {} => to_binary(&query_pause_state(deps.storage)),
+        QueryMsg::ListSwaps {
+            start_after,
+            limit,
+            side,
+            user,
+            token,
+        } => to_binary(&query_list_swaps(
+            deps.storage,
+            start_after,
+            limit,
+            side,
+            user,
+            token,
+        )),
+        QueryMsg::GetDestinationChain { chain_id } => {
+            to_binary(&query_destination_chain(deps.storage, chain_id))
+        }
+        QueryMsg::GetTokenRoute {
+            cur_token,
+            chain_id,
+        } => to_binary(&query_token_route(deps.storage, cur_token, chain_id)),
+    }
+}
+
+fn query_config(storage: &dyn Storage) -> StdResult<ConfigResponse> {
+    Ok(ConfigResponse {
+        owner: get_owner(storage)?,
+        signers: get_signers(storage),
+        threshold: get_threshold(storage),
+        signature_scheme: get_signature_scheme(storage, &SIGNATURE_SCHEME),
+    })
+}
+
+fn query_token_config(storage: &dyn Storage, token: String) -> TokenConfigResponse {
+    TokenConfigResponse {
+        accepted: is_accepted_token(storage, &token),
+        max_swap_amount: get_max_swap_amount(storage, &token),
+        mode: get_token_mode(storage, &token),
+        locked_balance: get_locked_balance(storage, &token),
+        volume_limit: get_volume_limit(storage, &token),
+        token,
     }
 }
 
-// test function for migrating
-fn query_test() -> Uint128 {
-    Uint128::new(100)
+fn query_token_configs(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> TokenConfigsResponse {
+    TokenConfigsResponse {
+        tokens: get_accepted_tokens(storage, start_after, limit)
+            .into_iter()
+            .map(|token| query_token_config(storage, token))
+            .collect(),
+    }
 }
 
-fn query_owner(storage: &dyn Storage) -> Addr {
+fn query_owner(storage: &dyn Storage) -> StdResult<Addr> {
     get_owner(storage)
 }
 
@@ -347,8 +1275,17 @@ fn query_operator(storage: &dyn Storage, operator: Addr) -> bool {
     is_operator(storage, operator)
 }
 
-fn query_signer(storage: &dyn Storage) -> Binary {
-    get_signer(storage)
+fn query_signer(storage: &dyn Storage, index: u32) -> SignerResponse {
+    SignerResponse {
+        public_key: get_signer(storage, index),
+    }
+}
+
+fn query_signers(storage: &dyn Storage) -> SignersResponse {
+    SignersResponse {
+        signers: get_signers(storage),
+        threshold: get_threshold(storage),
+    }
 }
 
 fn query_accepted_token(storage: &dyn Storage, token: String) -> bool {
@@ -367,6 +1304,96 @@ fn query_approve_transaction(storage: &dyn Storage, transaction_id: String) -> b
     is_approve_transaction(storage, &transaction_id)
 }
 
-fn query_swap_data(storage: &dyn Storage, transaction_id: String) -> Swapdata {
-    get_swap_data(storage, transaction_id)
+// source_chain defaults to this chain, where burns originate
+fn query_swap_data(
+    storage: &dyn Storage,
+    env: Env,
+    source_chain: Option<String>,
+    transaction_id: String,
+) -> Option<Swapdata> {
+    let source_chain = source_chain.unwrap_or(env.block.chain_id);
+    get_swap_data(storage, &source_chain, &transaction_id)
+}
+
+fn query_window_usage(storage: &dyn Storage, env: Env, token: String) -> WindowUsageResponse {
+    let limit = get_volume_limit(storage, &token);
+    WindowUsageResponse {
+        used: get_window_usage(storage, &token, env.block.time.seconds()),
+        max_amount: limit
+            .as_ref()
+            .map(|limit| limit.max_amount)
+            .unwrap_or_default(),
+        window: limit.map(|limit| limit.window).unwrap_or_default(),
+        token,
+    }
+}
+
+fn query_pending_mint(
+    storage: &dyn Storage,
+    source_chain: String,
+    transaction_id: String,
+) -> Option<PendingMint> {
+    get_pending_mint(storage, &source_chain, &transaction_id)
+}
+
+fn query_token_mode(storage: &dyn Storage, token: String) -> TokenMode {
+    get_token_mode(storage, &token)
+}
+
+fn query_locked_balance(storage: &dyn Storage, token: String) -> Uint128 {
+    get_locked_balance(storage, &token)
+}
+
+fn query_accepted_collection(storage: &dyn Storage, collection: String) -> bool {
+    is_accepted_collection(storage, &collection)
+}
+
+fn query_accepted_des_collection(storage: &dyn Storage, collection: String) -> bool {
+    is_accepted_des_collection(storage, &collection)
+}
+
+fn query_bridge_fee(storage: &dyn Storage, des_token: String) -> Option<BridgeFee> {
+    get_bridge_fee(storage, &des_token)
+}
+
+fn query_collected_fees(storage: &dyn Storage, asset: FeeAsset) -> Uint128 {
+    get_collected_fees(storage, &asset)
+}
+
+fn query_pause_state(storage: &dyn Storage) -> PauseStateResponse {
+    PauseStateResponse {
+        paused: is_globally_paused(storage),
+        mint_paused: is_direction_paused(storage, &Direction::Mint),
+        burn_paused: is_direction_paused(storage, &Direction::Burn),
+        paused_tokens: get_paused_tokens(storage),
+    }
+}
+
+fn query_list_swaps(
+    storage: &dyn Storage,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+    side: Option<String>,
+    user: Option<String>,
+    token: Option<String>,
+) -> SwapsResponse {
+    SwapsResponse {
+        swaps: list_swap_datas(storage, start_after, limit, side, user, token),
+    }
+}
+
+fn query_processed_status(
+    storage: &dyn Storage,
+    source_chain: String,
+    transaction_id: String,
+) -> Option<ProcessedStatus> {
+    get_processed_status(storage, &source_chain, &transaction_id)
+}
+
+fn query_destination_chain(storage: &dyn Storage, chain_id: String) -> Option<DestinationChain> {
+    get_destination_chain(storage, &chain_id)
+}
+
+fn query_token_route(storage: &dyn Storage, cur_token: String, chain_id: String) -> Option<String> {
+    get_token_route(storage, &cur_token, &chain_id)
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use monsterra_signing::SigningError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Signing(#[from] SigningError),
+
     #[error("Unauthorized")]
     Unauthorized {},
     // Add any other custom errors you like here.
@@ -14,6 +18,18 @@ pub enum ContractError {
     #[error("Internal")]
     Internal {},
 
+    #[error("InvalidContractName")]
+    InvalidContractName {},
+
+    #[error("InvalidContractVersion")]
+    InvalidContractVersion {},
+
+    #[error("CannotDowngrade")]
+    CannotDowngrade {},
+
+    #[error("MissingLegacySourceChain")]
+    MissingLegacySourceChain {},
+
     #[error("TransactionExisted")]
     TransactionExisted {},
 
@@ -26,6 +42,63 @@ pub enum ContractError {
     #[error("InvalidSignature")]
     InvalidSignature {},
 
+    #[error("SignerExisted")]
+    SignerExisted {},
+
+    #[error("SignerNotFound")]
+    SignerNotFound {},
+
+    #[error("InvalidThreshold")]
+    InvalidThreshold {},
+
+    #[error("SignatureExpired")]
+    SignatureExpired {},
+
+    #[error("NonceUsed")]
+    NonceUsed {},
+
+    #[error("TokenHasLockedBalance")]
+    TokenHasLockedBalance {},
+
+    #[error("InsufficientLockedBalance")]
+    InsufficientLockedBalance {},
+
+    #[error("ChainNotEnabled")]
+    ChainNotEnabled {},
+
+    #[error("RouteNotFound")]
+    RouteNotFound {},
+
+    #[error("InvalidDesUser")]
+    InvalidDesUser {},
+
+    #[error("SwapNotFound")]
+    SwapNotFound {},
+
+    #[error("AlreadyRefunded")]
+    AlreadyRefunded {},
+
+    #[error("Paused")]
+    Paused {},
+
+    #[error("InvalidFee")]
+    InvalidFee {},
+
+    #[error("FeeExceedsAmount")]
+    FeeExceedsAmount {},
+
+    #[error("InvalidFeePayment")]
+    InvalidFeePayment {},
+
+    #[error("InsufficientFees")]
+    InsufficientFees {},
+
+    #[error("InvalidVolumeLimit")]
+    InvalidVolumeLimit {},
+
+    #[error("PendingMintNotFound")]
+    PendingMintNotFound {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
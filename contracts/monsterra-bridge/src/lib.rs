@@ -1,6 +1,7 @@
 pub mod contract;
 pub mod error;
 pub mod msg;
+pub mod signing;
 pub mod state;
 
 pub use crate::error::ContractError;
@@ -2,7 +2,10 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Binary};
 
 #[allow(unused_imports)]
-use crate::state::Swapdata;
+use crate::state::{
+    AddressFormat, BridgeFee, DestinationChain, FeeAsset, PauseTarget, PendingMint,
+    ProcessedStatus, SignatureScheme, Swapdata, TokenMode, VolumeLimit,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -10,7 +13,11 @@ pub struct InstantiateMsg {
 } 
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // source chain that existing mint records are registered under, required
+    // when migrating from before 0.2.0 with any mint recorded
+    pub legacy_source_chain: Option<String>,
+}
 
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -21,16 +28,51 @@ pub enum ExecuteMsg {
     SetAcceptedDesToken {token:String, status: bool},
     SetMaxSwapAmount {token:String, max_amount: Uint128},
     SetApproveTransaction {transaction_id: String, status: bool},
-    SetSigner {public_key: Binary},
-    Mint {swap_message: SwapMessage, signature: Binary},
+    AddSigner {public_key: Binary},
+    RemoveSigner {index: u32},
+    SetThreshold {threshold: u32},
+    SetSignatureScheme {scheme: SignatureScheme},
+    // signatures are (signer index, signature) pairs over `signing::mint_signing_bytes`
+    Mint {swap_message: SwapMessage, payload: SignedPayload, signatures: Vec<(u32, Binary)>},
     Burn{swap_message: SwapMessage},
+    SetVolumeLimit {token: String, max_amount: Uint128, window: u64},
+    ReleasePendingMint {source_chain: String, transaction_id: String},
+    RejectPendingMint {source_chain: String, transaction_id: String},
+    SetTokenMode {token: String, mode: TokenMode},
+    SetAcceptedCollection {collection: String, status: bool},
+    SetAcceptedDesCollection {collection: String, status: bool},
+    // signatures are (signer index, signature) pairs over `signing::nft_mint_signing_bytes`
+    MintNft {nft_message: NftSwapMessage, payload: SignedPayload, signatures: Vec<(u32, Binary)>},
+    BurnNft {nft_message: NftSwapMessage},
+    // fee is None to remove the des_token's fee
+    SetBridgeFee {des_token: String, fee: Option<BridgeFee>},
+    WithdrawFees {asset: FeeAsset, recipient: String, amount: Uint128},
+    // signatures are (signer index, signature) pairs over `signing::refund_signing_bytes`
+    RefundBurn {transaction_id: String, expiry: u64, signatures: Vec<(u32, Binary)>},
+    SetDestinationChain {chain_id: String, address_format: AddressFormat, enabled: bool},
+    // des_token is None to remove the route
+    SetTokenRoute {cur_token: String, chain_id: String, des_token: Option<String>},
+    Pause {target: PauseTarget},
+    Unpause {target: PauseTarget},
     
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    // GetCount returns the current count as a json-encoded number
+    // owner and signer settings, the lists below are paged separately
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(TokenConfigResponse)]
+    TokenConfig {token: String},
+    // every token ever set through SetAcceptedToken
+    #[returns(TokenConfigsResponse)]
+    ListTokenConfigs{start_after: Option<String>, limit: Option<u32>},
+    #[returns(DestinationChainsResponse)]
+    ListDestinationChains{start_after: Option<String>, limit: Option<u32>},
+    // start_after is a chain_id
+    #[returns(TokenRoutesResponse)]
+    ListTokenRoutes{cur_token: String, start_after: Option<String>, limit: Option<u32>},
     #[returns(Addr)]
     GetOwner {},
     #[returns(bool)]
@@ -43,24 +85,145 @@ pub enum QueryMsg {
     IsAcceptedDesToken {token: String},
     #[returns(Uint128)]
     GetMaxSwapAmount {token: String},
-    #[returns(Binary)]
+    #[returns(bool)]
     IsApproveTransaction {transaction_id: String},
-    #[returns(Binary)]
-    GetSigner{},
-    #[returns(Swapdata)]
-    GetSwapData{transaction_id: String},
+    #[returns(SignerResponse)]
+    GetSigner{index: u32},
+    #[returns(SignersResponse)]
+    GetSigners{},
+    #[returns(SignatureScheme)]
+    GetSignatureScheme{},
+    // source_chain defaults to this chain, where burns originate
+    #[returns(Option<Swapdata>)]
+    GetSwapData{source_chain: Option<String>, transaction_id: String},
+    // user and token filter on cur_user and cur_token, start_after is a (source_chain, transaction_id) key
+    #[returns(SwapsResponse)]
+    ListSwaps{start_after: Option<(String, String)>, limit: Option<u32>, side: Option<String>, user: Option<String>, token: Option<String>},
+    #[returns(WindowUsageResponse)]
+    GetWindowUsage{token: String},
+    #[returns(Option<PendingMint>)]
+    GetPendingMint{source_chain: String, transaction_id: String},
+    #[returns(Option<ProcessedStatus>)]
+    GetProcessedStatus{source_chain: String, transaction_id: String},
+    #[returns(TokenMode)]
+    GetTokenMode{token: String},
+    #[returns(Uint128)]
+    GetLockedBalance{token: String},
+    #[returns(bool)]
+    IsAcceptedCollection{collection: String},
+    #[returns(bool)]
+    IsAcceptedDesCollection{collection: String},
+    #[returns(Option<BridgeFee>)]
+    GetBridgeFee{des_token: String},
     #[returns(Uint128)]
-    Test{}
+    GetCollectedFees{asset: FeeAsset},
+    #[returns(PauseStateResponse)]
+    GetPauseState{},
+    #[returns(Option<DestinationChain>)]
+    GetDestinationChain{chain_id: String},
+    #[returns(Option<String>)]
+    GetTokenRoute{cur_token: String, chain_id: String},
+
+}
+
+#[cw_serde]
+pub struct SignerResponse {
+    pub public_key: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub signers: Vec<(u32, Binary)>,
+    pub threshold: u32,
+    pub signature_scheme: SignatureScheme,
+}
+
+#[cw_serde]
+pub struct TokenConfigResponse {
+    pub token: String,
+    pub accepted: bool,
+    pub max_swap_amount: Uint128,
+    pub mode: TokenMode,
+    pub locked_balance: Uint128,
+    pub volume_limit: Option<VolumeLimit>,
+}
+
+#[cw_serde]
+pub struct TokenConfigsResponse {
+    pub tokens: Vec<TokenConfigResponse>,
+}
+
+#[cw_serde]
+pub struct DestinationChainsResponse {
+    pub chains: Vec<(String, DestinationChain)>,
+}
 
+#[cw_serde]
+pub struct TokenRoutesResponse {
+    // (chain_id, des_token) pairs
+    pub routes: Vec<(String, String)>,
+}
+
+#[cw_serde]
+pub struct SignersResponse {
+    pub signers: Vec<(u32, Binary)>,
+    pub threshold: u32,
+}
+
+#[cw_serde]
+pub struct SwapsResponse {
+    // keyed by (source_chain, transaction_id)
+    pub swaps: Vec<((String, String), Swapdata)>,
+}
+
+#[cw_serde]
+pub struct PauseStateResponse {
+    pub paused: bool,
+    pub mint_paused: bool,
+    pub burn_paused: bool,
+    pub paused_tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct WindowUsageResponse {
+    pub token: String,
+    pub used: Uint128,
+    pub max_amount: Uint128,
+    pub window: u64,
+}
+
+// Replay protection fields signed together with the swap message, each nonce
+// is accepted once per source chain
+#[cw_serde]
+pub struct SignedPayload {
+    pub source_chain: String,
+    pub nonce: u64,
+    pub expiry: u64,
 }
 
 // We define a custom struct for each query response
 #[cw_serde]
 pub struct SwapMessage{
     pub transaction_id: String,
+    // chain of des_token and des_user, the source chain when minting
+    pub des_chain: String,
     pub cur_token: String,
     pub des_token: String,
     pub cur_user: String,
     pub des_user: String,
     pub amount: Uint128
 }
+
+#[cw_serde]
+pub struct NftSwapMessage {
+    pub transaction_id: String,
+    // chain of des_collection and des_user, the source chain when minting
+    pub des_chain: String,
+    pub cur_collection: String,
+    pub des_collection: String,
+    pub cur_user: String,
+    pub des_user: String,
+    pub token_id: String,
+    pub token_uri: Option<String>,
+}
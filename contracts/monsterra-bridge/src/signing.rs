@@ -0,0 +1,140 @@
+use crate::msg::{NftSwapMessage, SignedPayload, SwapMessage};
+
+pub const SIGNING_DOMAIN: &str = "monsterra-bridge";
+pub const NFT_SIGNING_DOMAIN: &str = "monsterra-bridge-nft";
+pub const REFUND_SIGNING_DOMAIN: &str = "monsterra-bridge-refund";
+pub const SIGNING_VERSION: u8 = 1;
+
+// Builds the bytes a signer signs to authorize a mint.
+//
+// Layout, strings are prefixed with their length as a big-endian u32:
+// domain | version (u8) | chain_id | contract_address | source_chain | transaction_id
+// | cur_token | des_token | cur_user | des_user | amount (u128 BE) | nonce (u64 BE)
+// | expiry (u64 BE, unix seconds)
+pub fn mint_signing_bytes(
+    chain_id: &str,
+    contract_address: &str,
+    payload: &SignedPayload,
+    swap_message: &SwapMessage,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    push_str(&mut bytes, SIGNING_DOMAIN);
+    bytes.push(SIGNING_VERSION);
+    push_str(&mut bytes, chain_id);
+    push_str(&mut bytes, contract_address);
+    push_str(&mut bytes, &payload.source_chain);
+    push_str(&mut bytes, &swap_message.transaction_id);
+    push_str(&mut bytes, &swap_message.cur_token);
+    push_str(&mut bytes, &swap_message.des_token);
+    push_str(&mut bytes, &swap_message.cur_user);
+    push_str(&mut bytes, &swap_message.des_user);
+    bytes.extend_from_slice(&swap_message.amount.u128().to_be_bytes());
+    bytes.extend_from_slice(&payload.nonce.to_be_bytes());
+    bytes.extend_from_slice(&payload.expiry.to_be_bytes());
+    bytes
+}
+
+// Builds the bytes a signer signs to authorize an NFT mint.
+//
+// Layout, with strings encoded as in `mint_signing_bytes`:
+// nft domain | version (u8) | chain_id | contract_address | source_chain | transaction_id
+// | cur_collection | des_collection | cur_user | des_user | token_id
+// | token_uri (0u8 when absent, 1u8 followed by the string otherwise) | nonce (u64 BE)
+// | expiry (u64 BE, unix seconds)
+pub fn nft_mint_signing_bytes(
+    chain_id: &str,
+    contract_address: &str,
+    payload: &SignedPayload,
+    nft_message: &NftSwapMessage,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    push_str(&mut bytes, NFT_SIGNING_DOMAIN);
+    bytes.push(SIGNING_VERSION);
+    push_str(&mut bytes, chain_id);
+    push_str(&mut bytes, contract_address);
+    push_str(&mut bytes, &payload.source_chain);
+    push_str(&mut bytes, &nft_message.transaction_id);
+    push_str(&mut bytes, &nft_message.cur_collection);
+    push_str(&mut bytes, &nft_message.des_collection);
+    push_str(&mut bytes, &nft_message.cur_user);
+    push_str(&mut bytes, &nft_message.des_user);
+    push_str(&mut bytes, &nft_message.token_id);
+    match &nft_message.token_uri {
+        Some(token_uri) => {
+            bytes.push(1);
+            push_str(&mut bytes, token_uri);
+        }
+        None => bytes.push(0),
+    }
+    bytes.extend_from_slice(&payload.nonce.to_be_bytes());
+    bytes.extend_from_slice(&payload.expiry.to_be_bytes());
+    bytes
+}
+
+// Builds the bytes a signer signs to attest that a burn was never honoured on the
+// destination chain.
+//
+// Layout: refund domain | version (u8) | chain_id | contract_address | transaction_id
+// | expiry (u64 BE, unix seconds)
+pub fn refund_signing_bytes(
+    chain_id: &str,
+    contract_address: &str,
+    transaction_id: &str,
+    expiry: u64,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    push_str(&mut bytes, REFUND_SIGNING_DOMAIN);
+    bytes.push(SIGNING_VERSION);
+    push_str(&mut bytes, chain_id);
+    push_str(&mut bytes, contract_address);
+    push_str(&mut bytes, transaction_id);
+    bytes.extend_from_slice(&expiry.to_be_bytes());
+    bytes
+}
+
+fn push_str(bytes: &mut Vec<u8>, value: &str) {
+    bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Uint128;
+
+    #[test]
+    fn mint_signing_bytes_layout() {
+        let payload = SignedPayload {
+            source_chain: String::from("eth"),
+            nonce: 7,
+            expiry: 1700000000,
+        };
+        let swap_message = SwapMessage {
+            transaction_id: String::from("0xab"),
+            des_chain: String::from("eth"),
+            cur_token: String::from("ct"),
+            des_token: String::from("dt"),
+            cur_user: String::from("cu"),
+            des_user: String::from("du"),
+            amount: Uint128::new(1000),
+        };
+        let bytes = mint_signing_bytes("chain-1", "contract", &payload, &swap_message);
+
+        let mut expected: Vec<u8> = vec![];
+        expected.extend_from_slice(b"\x00\x00\x00\x10monsterra-bridge");
+        expected.extend_from_slice(b"\x01");
+        expected.extend_from_slice(b"\x00\x00\x00\x07chain-1");
+        expected.extend_from_slice(b"\x00\x00\x00\x08contract");
+        expected.extend_from_slice(b"\x00\x00\x00\x03eth");
+        expected.extend_from_slice(b"\x00\x00\x00\x040xab");
+        expected.extend_from_slice(b"\x00\x00\x00\x02ct");
+        expected.extend_from_slice(b"\x00\x00\x00\x02dt");
+        expected.extend_from_slice(b"\x00\x00\x00\x02cu");
+        expected.extend_from_slice(b"\x00\x00\x00\x02du");
+        expected.extend_from_slice(&[0; 14]);
+        expected.extend_from_slice(b"\x03\xe8");
+        expected.extend_from_slice(b"\x00\x00\x00\x00\x00\x00\x00\x07");
+        expected.extend_from_slice(b"\x00\x00\x00\x00\x65\x53\xf1\x00");
+        assert_eq!(bytes, expected);
+    }
+}
@@ -1,10 +1,16 @@
 use crate::error::ContractError;
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{to_binary, Addr, Binary, MessageInfo, Response, StdResult, Storage, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp,
+    Uint128,
+};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+pub use monsterra_signing::SignatureScheme;
+use monsterra_signing::{get_signature_scheme, validate_signer};
+use sha3::{Digest, Keccak256};
 
-use crate::msg::SwapMessage;
+use crate::msg::{NftSwapMessage, SwapMessage};
 
 #[cw_serde]
 pub struct Swapdata {
@@ -14,10 +20,19 @@ pub struct Swapdata {
     pub des_user: String,
     pub amount: Uint128,
     pub side: String,
+    // set for NFT swaps, where the tokens are collections and amount is 1
+    pub token_id: Option<String>,
+    // unset on swaps recorded before block info was tracked
+    pub block_height: Option<u64>,
+    pub block_time: Option<Timestamp>,
+    #[serde(default)]
+    pub refunded: bool,
+    // unset on NFT swaps and swaps recorded before chain routing
+    pub des_chain: Option<String>,
 }
 
 impl Swapdata {
-    pub fn default(swap_message: &SwapMessage, side: String) -> Swapdata {
+    pub fn default(swap_message: &SwapMessage, side: String, block: &BlockInfo) -> Swapdata {
         Swapdata {
             cur_token: swap_message.cur_token.to_string(),
             des_token: swap_message.des_token.to_string(),
@@ -25,28 +40,190 @@ impl Swapdata {
             des_user: swap_message.des_user.to_string(),
             amount: swap_message.amount,
             side: side,
+            token_id: None,
+            block_height: Some(block.height),
+            block_time: Some(block.time),
+            refunded: false,
+            des_chain: Some(swap_message.des_chain.to_string()),
         }
     }
 
-    pub fn empty_default() -> Swapdata {
+    pub fn nft(nft_message: &NftSwapMessage, side: String, block: &BlockInfo) -> Swapdata {
         Swapdata {
-            cur_token: String::new(),
-            des_token: String::new(),
-            cur_user: String::new(),
-            des_user: String::new(),
-            amount: Uint128::new(0),
-            side: String::from("NaN"),
+            cur_token: nft_message.cur_collection.to_string(),
+            des_token: nft_message.des_collection.to_string(),
+            cur_user: nft_message.cur_user.to_string(),
+            des_user: nft_message.des_user.to_string(),
+            amount: Uint128::new(1),
+            side,
+            token_id: Some(nft_message.token_id.to_string()),
+            block_height: Some(block.height),
+            block_time: Some(block.time),
+            refunded: false,
+            des_chain: Some(nft_message.des_chain.to_string()),
         }
     }
 }
 
+// Mint volume of a token allowed within a rolling window, zero means unlimited
+#[cw_serde]
+pub struct VolumeLimit {
+    pub max_amount: Uint128,
+    pub window: u64,
+}
+
+#[cw_serde]
+pub enum PendingMintStatus {
+    Pending,
+    Released,
+    Rejected,
+}
+
+#[cw_serde]
+pub enum ProcessedStatus {
+    Pending,
+    Completed,
+    Refunded,
+}
+
+// Mint held back because it would exceed the token's volume limit
+#[cw_serde]
+pub struct PendingMint {
+    pub swap_message: SwapMessage,
+    pub source_chain: String,
+    pub queued_at: u64,
+    pub status: PendingMintStatus,
+}
+
+// How the bridge moves a token: mint and burn it, or hold it in escrow
+#[cw_serde]
+pub enum TokenMode {
+    MintBurn,
+    LockRelease,
+}
+
+// Outbound fee for a des_token, amounts are in the fee asset: the bridged token
+// when native_denom is None, otherwise that native coin. A native fee is the flat
+// fixed amount, bps of the bridged token cannot be expressed in another denom.
+#[cw_serde]
+pub struct BridgeFee {
+    pub fixed: Uint128,
+    pub bps: u16,
+    pub native_denom: Option<String>,
+}
+
+impl BridgeFee {
+    pub fn compute(&self, amount: Uint128) -> StdResult<Uint128> {
+        match self.native_denom {
+            Some(_) => Ok(self.fixed),
+            None => self
+                .fixed
+                .checked_add(amount.multiply_ratio(self.bps, MAX_FEE_BPS))
+                .map_err(StdError::overflow),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum FeeAsset {
+    Cw20 { address: String },
+    Native { denom: String },
+}
+
+impl FeeAsset {
+    pub fn key(&self) -> String {
+        match self {
+            FeeAsset::Cw20 { address } => format!("cw20:{}", address),
+            FeeAsset::Native { denom } => format!("native:{}", denom),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum Direction {
+    Mint,
+    Burn,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Direction::Mint => "mint",
+            Direction::Burn => "burn",
+        }
+    }
+}
+
+#[cw_serde]
+pub enum PauseTarget {
+    Global,
+    Token { token: String },
+    Direction { direction: Direction },
+}
+
+#[cw_serde]
+pub enum AddressFormat {
+    // 0x prefixed hex, mixed case addresses must match their EIP-55 checksum
+    Evm,
+    Bech32 { prefix: String },
+    Any,
+}
+
+impl AddressFormat {
+    pub fn is_valid(&self, address: &str) -> bool {
+        match self {
+            AddressFormat::Evm => is_valid_evm_address(address),
+            AddressFormat::Bech32 { prefix } => is_valid_bech32_address(address, prefix),
+            AddressFormat::Any => !address.is_empty(),
+        }
+    }
+}
+
+fn is_valid_evm_address(address: &str) -> bool {
+    let hex = match address.strip_prefix("0x") {
+        Some(value) => value,
+        None => return false,
+    };
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    if hex == hex.to_lowercase() || hex == hex.to_uppercase() {
+        return true;
+    }
+
+    let hash = Keccak256::digest(hex.to_lowercase().as_bytes());
+    hex.chars().enumerate().all(|(i, c)| {
+        let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
+// Checks the shape only, the checksum is verified by the destination chain
+fn is_valid_bech32_address(address: &str, prefix: &str) -> bool {
+    const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    let data = match address
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('1'))
+    {
+        Some(value) => value,
+        None => return false,
+    };
+    data.len() >= 6 && address.len() <= 90 && data.chars().all(|c| CHARSET.contains(c))
+}
+
+#[cw_serde]
+pub struct DestinationChain {
+    pub address_format: AddressFormat,
+    pub enabled: bool,
+}
+
 //getter setter of administration data
 pub fn set_new_owner(
     storage: &mut dyn Storage,
     info: &MessageInfo,
     user: Addr,
 ) -> Result<Response, ContractError> {
-    if info.sender != get_owner(storage) {
+    if info.sender != get_owner(storage)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -57,8 +234,8 @@ pub fn set_new_owner(
     }
 }
 
-pub fn get_owner(storage: &dyn Storage) -> Addr {
-    OWNER.load(storage).unwrap()
+pub fn get_owner(storage: &dyn Storage) -> StdResult<Addr> {
+    OWNER.load(storage)
 }
 
 pub fn set_admin(
@@ -67,7 +244,7 @@ pub fn set_admin(
     user: Addr,
     status: bool,
 ) -> Result<Response, ContractError> {
-    if info.sender != get_owner(storage) {
+    if info.sender != get_owner(storage)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -89,7 +266,7 @@ pub fn is_admin(storage: &dyn Storage, user: Addr) -> bool {
     }
 }
 
-pub fn set_signer(
+pub fn add_signer(
     storage: &mut dyn Storage,
     info: &MessageInfo,
     public_key: Binary,
@@ -97,25 +274,103 @@ pub fn set_signer(
     if !is_admin(storage, info.sender.clone()) {
         return Err(ContractError::Unauthorized {});
     }
+    validate_signer(
+        &get_signature_scheme(storage, &SIGNATURE_SCHEME),
+        &public_key,
+    )?;
 
-    let result = SIGNER.save(storage, &public_key);
-    match result {
-        Ok(_) => Ok(Response::new()
-            .add_attribute("method", "set_signer")
-            .add_attribute("public_key", public_key.to_string())),
-        Err(_) => Err(ContractError::Internal {}),
+    if get_signers(storage)
+        .iter()
+        .any(|(_, key)| *key == public_key)
+    {
+        return Err(ContractError::SignerExisted {});
     }
+
+    // indexes are never reused so relayers can keep referring to them
+    let index = NEXT_SIGNER_INDEX.may_load(storage)?.unwrap_or(0);
+    SIGNERS.save(storage, index, &public_key)?;
+    NEXT_SIGNER_INDEX.save(storage, &(index + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("method", "add_signer")
+        .add_attribute("index", index.to_string())
+        .add_attribute("public_key", public_key.to_string()))
 }
 
-pub fn get_signer(storage: &dyn Storage) -> Binary {
-    let result = SIGNER.load(storage);
-    match result {
-        Ok(value) => value,
-        Err(_) => {
-            let mes = "";
-            to_binary(&mes).unwrap()
-        }
+pub fn remove_signer(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    index: u32,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !SIGNERS.has(storage, index) {
+        return Err(ContractError::SignerNotFound {});
+    }
+
+    if (get_signers(storage).len() as u32) - 1 < get_threshold(storage) {
+        return Err(ContractError::InvalidThreshold {});
     }
+
+    SIGNERS.remove(storage, index);
+
+    Ok(Response::new()
+        .add_attribute("method", "remove_signer")
+        .add_attribute("index", index.to_string()))
+}
+
+pub fn set_threshold(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if threshold == 0 || threshold > get_signers(storage).len() as u32 {
+        return Err(ContractError::InvalidThreshold {});
+    }
+
+    THRESHOLD.save(storage, &threshold)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_threshold")
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub fn get_signer(storage: &dyn Storage, index: u32) -> Option<Binary> {
+    SIGNERS.may_load(storage, index).ok().flatten()
+}
+
+// Moves the single signer of versions before 0.2.0 into the signer set
+pub fn migrate_legacy_signer(storage: &mut dyn Storage) -> StdResult<()> {
+    let public_key = match LEGACY_SIGNER.may_load(storage)? {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    if NEXT_SIGNER_INDEX.may_load(storage)?.is_none() {
+        SIGNERS.save(storage, 0, &public_key)?;
+        NEXT_SIGNER_INDEX.save(storage, &1)?;
+    }
+    if THRESHOLD.may_load(storage)?.is_none() {
+        THRESHOLD.save(storage, &1)?;
+    }
+    LEGACY_SIGNER.remove(storage);
+    Ok(())
+}
+
+pub fn get_signers(storage: &dyn Storage) -> Vec<(u32, Binary)> {
+    SIGNERS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .collect()
+}
+
+pub fn get_threshold(storage: &dyn Storage) -> u32 {
+    THRESHOLD.may_load(storage).ok().flatten().unwrap_or(0)
 }
 
 pub fn set_accepted_token(
@@ -138,6 +393,20 @@ pub fn set_accepted_token(
     }
 }
 
+pub fn get_accepted_tokens(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<String> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    ACCEPTED_TOKENS
+        .keys(storage, start, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .take(limit)
+        .collect()
+}
+
 pub fn is_accepted_token(storage: &dyn Storage, token: &String) -> bool {
     let result = ACCEPTED_TOKENS.load(storage, token.to_string());
     match result {
@@ -174,22 +443,205 @@ pub fn is_accepted_des_token(storage: &dyn Storage, token: &String) -> bool {
     }
 }
 
-//getter setter of swapdata
+pub fn set_accepted_collection(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    collection: String,
+    status: bool,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ACCEPTED_COLLECTIONS.save(storage, collection.clone(), &status)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_accepted_collection")
+        .add_attribute("accepted_collection", collection)
+        .add_attribute("status", status.to_string()))
+}
+
+pub fn is_accepted_collection(storage: &dyn Storage, collection: &String) -> bool {
+    ACCEPTED_COLLECTIONS
+        .may_load(storage, collection.to_string())
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+pub fn set_accepted_des_collection(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    collection: String,
+    status: bool,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    DES_ACCEPTED_COLLECTIONS.save(storage, collection.clone(), &status)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_accepted_des_collection")
+        .add_attribute("des_accepted_collection", collection)
+        .add_attribute("status", status.to_string()))
+}
+
+pub fn is_accepted_des_collection(storage: &dyn Storage, collection: &String) -> bool {
+    DES_ACCEPTED_COLLECTIONS
+        .may_load(storage, collection.to_string())
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+//getter setter of swapdata, keyed by the chain the transaction originated on
 pub fn set_swap_data(
     storage: &mut dyn Storage,
-    transaction_id: String,
+    source_chain: &str,
+    transaction_id: &str,
     swapdata: Swapdata,
 ) -> StdResult<()> {
-    SWAPDATAS.save(storage, transaction_id, &swapdata)
+    swap_datas().save(
+        storage,
+        (source_chain.to_string(), transaction_id.to_string()),
+        &swapdata,
+    )
 }
 
-pub fn get_swap_data(storage: &dyn Storage, transaction_id: String) -> Swapdata {
-    let result = SWAPDATAS.load(storage, transaction_id);
+pub fn get_swap_data(
+    storage: &dyn Storage,
+    source_chain: &str,
+    transaction_id: &str,
+) -> Option<Swapdata> {
+    swap_datas()
+        .may_load(
+            storage,
+            (source_chain.to_string(), transaction_id.to_string()),
+        )
+        .ok()
+        .flatten()
+}
 
-    match result {
-        Ok(value) => value,
-        Err(_) => Swapdata::empty_default(),
+// Serves from the most selective index given, the other filters are applied on top
+pub fn list_swap_datas(
+    storage: &dyn Storage,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+    side: Option<String>,
+    user: Option<String>,
+    token: Option<String>,
+) -> Vec<((String, String), Swapdata)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let swaps = swap_datas();
+    let iter = match (&user, &token, &side) {
+        (Some(user), _, _) => {
+            swaps
+                .idx
+                .user
+                .prefix(user.clone())
+                .range(storage, start, None, Order::Ascending)
+        }
+        (None, Some(token), _) => {
+            swaps
+                .idx
+                .token
+                .prefix(token.clone())
+                .range(storage, start, None, Order::Ascending)
+        }
+        (None, None, Some(side)) => {
+            swaps
+                .idx
+                .side
+                .prefix(side.clone())
+                .range(storage, start, None, Order::Ascending)
+        }
+        (None, None, None) => swaps.range(storage, start, None, Order::Ascending),
+    };
+    iter.filter_map(|item| item.ok())
+        .filter(|(_, swap)| {
+            (side.is_none() || side.as_ref() == Some(&swap.side))
+                && (token.is_none() || token.as_ref() == Some(&swap.cur_token))
+        })
+        .take(limit)
+        .collect()
+}
+
+// Replay registry, keyed by the chain the transaction originated on
+pub fn set_processed_status(
+    storage: &mut dyn Storage,
+    source_chain: &str,
+    transaction_id: &str,
+    status: ProcessedStatus,
+) -> StdResult<()> {
+    PROCESSED_TRANSACTIONS.save(
+        storage,
+        (source_chain.to_string(), transaction_id.to_string()),
+        &status,
+    )
+}
+
+pub fn get_processed_status(
+    storage: &dyn Storage,
+    source_chain: &str,
+    transaction_id: &str,
+) -> Option<ProcessedStatus> {
+    PROCESSED_TRANSACTIONS
+        .may_load(
+            storage,
+            (source_chain.to_string(), transaction_id.to_string()),
+        )
+        .ok()
+        .flatten()
+}
+
+pub fn is_nonce_used(storage: &dyn Storage, source_chain: &str, nonce: u64) -> bool {
+    USED_NONCES
+        .may_load(storage, (source_chain.to_string(), nonce))
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+pub fn set_nonce_used(storage: &mut dyn Storage, source_chain: &str, nonce: u64) -> StdResult<()> {
+    USED_NONCES.save(storage, (source_chain.to_string(), nonce), &true)
+}
+
+// Moves swaps recorded by transaction id alone under their source chain, indexing
+// them, and registers them as processed. Burns originated here, mints are
+// registered under legacy_source_chain since their origin was not kept.
+// Mint records do not say which chain they came from, so they need
+// `legacy_source_chain`, otherwise they could not be found to stop a replay
+pub fn migrate_legacy_swap_datas(
+    storage: &mut dyn Storage,
+    chain_id: &str,
+    legacy_source_chain: Option<&str>,
+) -> Result<(), ContractError> {
+    let swaps = LEGACY_SWAPDATAS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (transaction_id, swap) in swaps {
+        let source_chain = if swap.side.starts_with("burn") {
+            chain_id
+        } else {
+            match legacy_source_chain {
+                Some(value) if !value.is_empty() => value,
+                _ => return Err(ContractError::MissingLegacySourceChain {}),
+            }
+        };
+        LEGACY_SWAPDATAS.remove(storage, transaction_id.clone());
+        set_swap_data(storage, source_chain, &transaction_id, swap)?;
+        if get_processed_status(storage, source_chain, &transaction_id).is_none() {
+            set_processed_status(
+                storage,
+                source_chain,
+                &transaction_id,
+                ProcessedStatus::Completed,
+            )?;
+        }
     }
+    Ok(())
 }
 
 pub fn set_max_swap_amount(
@@ -278,13 +730,632 @@ pub fn is_operator(storage: &dyn Storage, operator: Addr) -> bool {
     }
 }
 
+pub fn set_volume_limit(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    token: String,
+    max_amount: Uint128,
+    window: u64,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if window == 0 {
+        return Err(ContractError::InvalidVolumeLimit {});
+    }
+
+    VOLUME_LIMITS.save(storage, token.clone(), &VolumeLimit { max_amount, window })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_volume_limit")
+        .add_attribute("token", token)
+        .add_attribute("max_amount", max_amount.to_string())
+        .add_attribute("window", window.to_string()))
+}
+
+pub fn get_volume_limit(storage: &dyn Storage, token: &String) -> Option<VolumeLimit> {
+    VOLUME_LIMITS
+        .may_load(storage, token.to_string())
+        .ok()
+        .flatten()
+}
+
+// Usage is tracked in hourly buckets, so the window start is rounded down to a bucket
+pub fn get_window_usage(storage: &dyn Storage, token: &String, now: u64) -> Uint128 {
+    let window = match get_volume_limit(storage, token) {
+        Some(limit) => limit.window,
+        None => return Uint128::zero(),
+    };
+    let start_bucket = now.saturating_sub(window) / VOLUME_BUCKET_SECONDS;
+    MINT_VOLUMES
+        .prefix(token.to_string())
+        .range(
+            storage,
+            Some(Bound::inclusive(start_bucket)),
+            None,
+            Order::Ascending,
+        )
+        .filter_map(|item| item.ok())
+        .fold(Uint128::zero(), |total, (_, amount)| total + amount)
+}
+
+pub fn record_mint_volume(
+    storage: &mut dyn Storage,
+    token: &String,
+    amount: Uint128,
+    now: u64,
+) -> StdResult<()> {
+    let limit = match get_volume_limit(storage, token) {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    // drop buckets that fell out of the window
+    let start_bucket = now.saturating_sub(limit.window) / VOLUME_BUCKET_SECONDS;
+    let expired = MINT_VOLUMES
+        .prefix(token.to_string())
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(start_bucket)),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<_>>>()?;
+    for bucket in expired {
+        MINT_VOLUMES.remove(storage, (token.to_string(), bucket));
+    }
+
+    let bucket = now / VOLUME_BUCKET_SECONDS;
+    MINT_VOLUMES.update(storage, (token.to_string(), bucket), |volume| {
+        Ok::<Uint128, cosmwasm_std::StdError>(volume.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+pub fn is_within_volume_limit(
+    storage: &dyn Storage,
+    token: &String,
+    amount: Uint128,
+    now: u64,
+) -> bool {
+    match get_volume_limit(storage, token) {
+        Some(limit) if !limit.max_amount.is_zero() => {
+            get_window_usage(storage, token, now) + amount <= limit.max_amount
+        }
+        _ => true,
+    }
+}
+
+pub fn set_pending_mint(
+    storage: &mut dyn Storage,
+    source_chain: &str,
+    transaction_id: &str,
+    pending_mint: &PendingMint,
+) -> StdResult<()> {
+    PENDING_MINTS.save(
+        storage,
+        (source_chain.to_string(), transaction_id.to_string()),
+        pending_mint,
+    )
+}
+
+pub fn get_pending_mint(
+    storage: &dyn Storage,
+    source_chain: &str,
+    transaction_id: &str,
+) -> Option<PendingMint> {
+    PENDING_MINTS
+        .may_load(
+            storage,
+            (source_chain.to_string(), transaction_id.to_string()),
+        )
+        .ok()
+        .flatten()
+}
+
+pub fn set_token_mode(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    token: String,
+    mode: TokenMode,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // escrowed funds would be stranded once the token stops releasing
+    if mode == TokenMode::MintBurn && !get_locked_balance(storage, &token).is_zero() {
+        return Err(ContractError::TokenHasLockedBalance {});
+    }
+
+    TOKEN_MODES.save(storage, token.clone(), &mode)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_token_mode")
+        .add_attribute("token", token))
+}
+
+pub fn get_token_mode(storage: &dyn Storage, token: &String) -> TokenMode {
+    TOKEN_MODES
+        .may_load(storage, token.to_string())
+        .ok()
+        .flatten()
+        .unwrap_or(TokenMode::MintBurn)
+}
+
+pub fn get_locked_balance(storage: &dyn Storage, token: &String) -> Uint128 {
+    LOCKED_BALANCES
+        .may_load(storage, token.to_string())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn increase_locked_balance(
+    storage: &mut dyn Storage,
+    token: &String,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    LOCKED_BALANCES.update(storage, token.to_string(), |balance| {
+        Ok(balance.unwrap_or_default() + amount)
+    })
+}
+
+pub fn decrease_locked_balance(
+    storage: &mut dyn Storage,
+    token: &String,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let balance = get_locked_balance(storage, token);
+    if balance < amount {
+        return Err(ContractError::InsufficientLockedBalance {});
+    }
+    let balance = balance - amount;
+    LOCKED_BALANCES.save(storage, token.to_string(), &balance)?;
+    Ok(balance)
+}
+
+pub fn set_bridge_fee(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    des_token: String,
+    fee: Option<BridgeFee>,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match fee {
+        Some(fee) => {
+            // a fee taken out of the bridged token must leave some amount to bridge,
+            // which a 100% rate never does
+            let invalid = match fee.native_denom {
+                Some(_) => fee.bps != 0,
+                None => fee.bps >= MAX_FEE_BPS || fee.fixed == Uint128::MAX,
+            };
+            if invalid {
+                return Err(ContractError::InvalidFee {});
+            }
+            BRIDGE_FEES.save(storage, des_token.clone(), &fee)?;
+        }
+        None => BRIDGE_FEES.remove(storage, des_token.clone()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_bridge_fee")
+        .add_attribute("des_token", des_token))
+}
+
+pub fn get_bridge_fee(storage: &dyn Storage, des_token: &String) -> Option<BridgeFee> {
+    BRIDGE_FEES
+        .may_load(storage, des_token.to_string())
+        .ok()
+        .flatten()
+}
+
+pub fn get_collected_fees(storage: &dyn Storage, asset: &FeeAsset) -> Uint128 {
+    COLLECTED_FEES
+        .may_load(storage, asset.key())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+pub fn add_collected_fees(
+    storage: &mut dyn Storage,
+    asset: &FeeAsset,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    COLLECTED_FEES.update(storage, asset.key(), |balance| {
+        Ok(balance.unwrap_or_default() + amount)
+    })
+}
+
+pub fn withdraw_collected_fees(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    asset: &FeeAsset,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let balance = get_collected_fees(storage, asset);
+    if balance < amount {
+        return Err(ContractError::InsufficientFees {});
+    }
+    COLLECTED_FEES.save(storage, asset.key(), &(balance - amount))?;
+    Ok(())
+}
+
+// Operators can pause to halt the bridge quickly, only the owner can unpause
+pub fn set_paused(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    target: PauseTarget,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let is_owner = info.sender == get_owner(storage)?;
+    let can_pause = is_owner
+        || is_admin(storage, info.sender.clone())
+        || is_operator(storage, info.sender.clone());
+    if (paused && !can_pause) || (!paused && !is_owner) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let target_name = match &target {
+        PauseTarget::Global => {
+            PAUSED.save(storage, &paused)?;
+            String::from("global")
+        }
+        PauseTarget::Token { token } => {
+            PAUSED_TOKENS.save(storage, token.clone(), &paused)?;
+            token.clone()
+        }
+        PauseTarget::Direction { direction } => {
+            PAUSED_DIRECTIONS.save(storage, direction.as_str().to_string(), &paused)?;
+            direction.as_str().to_string()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", if paused { "pause" } else { "unpause" })
+        .add_attribute("target", target_name))
+}
+
+pub fn is_paused(storage: &dyn Storage, token: &String, direction: &Direction) -> bool {
+    is_globally_paused(storage)
+        || is_direction_paused(storage, direction)
+        || PAUSED_TOKENS
+            .may_load(storage, token.to_string())
+            .ok()
+            .flatten()
+            .unwrap_or(false)
+}
+
+pub fn is_globally_paused(storage: &dyn Storage) -> bool {
+    PAUSED.may_load(storage).ok().flatten().unwrap_or(false)
+}
+
+pub fn is_direction_paused(storage: &dyn Storage, direction: &Direction) -> bool {
+    PAUSED_DIRECTIONS
+        .may_load(storage, direction.as_str().to_string())
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
+
+pub fn get_paused_tokens(storage: &dyn Storage) -> Vec<String> {
+    PAUSED_TOKENS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .filter(|(_, paused)| *paused)
+        .map(|(token, _)| token)
+        .collect()
+}
+
+pub fn set_destination_chain(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    chain_id: String,
+    address_format: AddressFormat,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    DES_CHAINS.save(
+        storage,
+        chain_id.clone(),
+        &DestinationChain {
+            address_format,
+            enabled,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_destination_chain")
+        .add_attribute("chain_id", chain_id)
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn get_destination_chain(storage: &dyn Storage, chain_id: &String) -> Option<DestinationChain> {
+    DES_CHAINS
+        .may_load(storage, chain_id.to_string())
+        .ok()
+        .flatten()
+}
+
+pub fn get_destination_chains(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<(String, DestinationChain)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    DES_CHAINS
+        .range(storage, start, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .take(limit)
+        .collect()
+}
+
+pub fn set_token_route(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    cur_token: String,
+    chain_id: String,
+    des_token: Option<String>,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = (cur_token.clone(), chain_id.clone());
+    match &des_token {
+        Some(des_token) => TOKEN_ROUTES.save(storage, key, des_token)?,
+        None => TOKEN_ROUTES.remove(storage, key),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_token_route")
+        .add_attribute("cur_token", cur_token)
+        .add_attribute("chain_id", chain_id)
+        .add_attribute("des_token", des_token.unwrap_or_default()))
+}
+
+pub fn get_token_route(
+    storage: &dyn Storage,
+    cur_token: &String,
+    chain_id: &String,
+) -> Option<String> {
+    TOKEN_ROUTES
+        .may_load(storage, (cur_token.to_string(), chain_id.to_string()))
+        .ok()
+        .flatten()
+}
+
+// (chain_id, des_token) routes of a cur_token
+pub fn get_token_routes(
+    storage: &dyn Storage,
+    cur_token: &String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<(String, String)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    TOKEN_ROUTES
+        .prefix(cur_token.to_string())
+        .range(storage, start, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .take(limit)
+        .collect()
+}
+
 pub const OWNER: Item<Addr> = Item::new("owner");
 pub const ADMIN: Map<Addr, bool> = Map::new("admin");
-pub const SIGNER: Item<Binary> = Item::new("signer");
+pub const LEGACY_SIGNER: Item<Binary> = Item::new("signer");
+pub const SIGNERS: Map<u32, Binary> = Map::new("signers");
+pub const NEXT_SIGNER_INDEX: Item<u32> = Item::new("nextSignerIndex");
+pub const THRESHOLD: Item<u32> = Item::new("threshold");
+pub const SIGNATURE_SCHEME: Item<SignatureScheme> = Item::new("signatureScheme");
+
+pub const PROCESSED_TRANSACTIONS: Map<(String, String), ProcessedStatus> =
+    Map::new("processedTransactions");
+// Nonces of signed mint payloads already consumed, per source chain
+pub const USED_NONCES: Map<(String, u64), bool> = Map::new("usedNonces");
+
+// Swaps as stored before they were keyed by source chain, only read by `migrate`
+pub const LEGACY_SWAPDATAS: Map<String, Swapdata> = Map::new("swapdata");
+
+pub const SWAPDATAS_KEY: &str = "swapDatas";
+
+pub struct SwapdataIndexes<'a> {
+    pub side: MultiIndex<'a, String, Swapdata, (String, String)>,
+    pub user: MultiIndex<'a, String, Swapdata, (String, String)>,
+    pub token: MultiIndex<'a, String, Swapdata, (String, String)>,
+}
+
+impl<'a> IndexList<Swapdata> for SwapdataIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Swapdata>> + '_> {
+        let v: Vec<&dyn Index<Swapdata>> = vec![&self.side, &self.user, &self.token];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn swap_side_idx(d: &Swapdata) -> String {
+    d.side.clone()
+}
+
+pub fn swap_user_idx(d: &Swapdata) -> String {
+    d.cur_user.clone()
+}
 
-pub const SWAPDATAS: Map<String, Swapdata> = Map::new("swapdata");
+pub fn swap_token_idx(d: &Swapdata) -> String {
+    d.cur_token.clone()
+}
+
+pub fn swap_datas<'a>() -> IndexedMap<'a, (String, String), Swapdata, SwapdataIndexes<'a>> {
+    let indexes = SwapdataIndexes {
+        side: MultiIndex::new(swap_side_idx, SWAPDATAS_KEY, "swapDatas__side"),
+        user: MultiIndex::new(swap_user_idx, SWAPDATAS_KEY, "swapDatas__user"),
+        token: MultiIndex::new(swap_token_idx, SWAPDATAS_KEY, "swapDatas__token"),
+    };
+    IndexedMap::new(SWAPDATAS_KEY, indexes)
+}
+
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
 pub const ACCEPTED_TOKENS: Map<String, bool> = Map::new("acceptedToken");
 pub const DES_ACCEPTED_TOKENS: Map<String, bool> = Map::new("desAcceptedToken");
+pub const ACCEPTED_COLLECTIONS: Map<String, bool> = Map::new("acceptedCollections");
+pub const DES_ACCEPTED_COLLECTIONS: Map<String, bool> = Map::new("desAcceptedCollections");
 pub const MAX_SWAP_AMOUNTS: Map<String, Uint128> = Map::new("maxSwapAmounts");
 pub const APPROVED_TRANSACTIONS: Map<String, bool> = Map::new("approvedTransactions");
 pub const OPERATORS: Map<Addr, bool> = Map::new("operators");
+
+pub const VOLUME_BUCKET_SECONDS: u64 = 3600;
+pub const VOLUME_LIMITS: Map<String, VolumeLimit> = Map::new("volumeLimits");
+pub const MINT_VOLUMES: Map<(String, u64), Uint128> = Map::new("mintVolumes");
+pub const PENDING_MINTS: Map<(String, String), PendingMint> = Map::new("pendingMints");
+pub const TOKEN_MODES: Map<String, TokenMode> = Map::new("tokenModes");
+pub const LOCKED_BALANCES: Map<String, Uint128> = Map::new("lockedBalances");
+
+pub const DES_CHAINS: Map<String, DestinationChain> = Map::new("desChains");
+pub const TOKEN_ROUTES: Map<(String, String), String> = Map::new("tokenRoutes");
+
+pub const PAUSED: Item<bool> = Item::new("paused");
+pub const PAUSED_TOKENS: Map<String, bool> = Map::new("pausedTokens");
+pub const PAUSED_DIRECTIONS: Map<String, bool> = Map::new("pausedDirections");
+
+pub const MAX_FEE_BPS: u16 = 10000;
+pub const BRIDGE_FEES: Map<String, BridgeFee> = Map::new("bridgeFees");
+pub const COLLECTED_FEES: Map<String, Uint128> = Map::new("collectedFees");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn legacy_mints_need_a_source_chain() {
+        let mut storage = MockStorage::new();
+        let mint = Swapdata {
+            cur_token: "token".to_string(),
+            des_token: "des_token".to_string(),
+            cur_user: "user".to_string(),
+            des_user: "des_user".to_string(),
+            amount: Uint128::new(100),
+            side: "mint".to_string(),
+            token_id: None,
+            block_height: None,
+            block_time: None,
+            refunded: false,
+            des_chain: None,
+        };
+        LEGACY_SWAPDATAS
+            .save(&mut storage, "tx".to_string(), &mint)
+            .unwrap();
+
+        assert!(matches!(
+            migrate_legacy_swap_datas(&mut storage, "this-chain", None),
+            Err(ContractError::MissingLegacySourceChain {})
+        ));
+        assert!(matches!(
+            migrate_legacy_swap_datas(&mut storage, "this-chain", Some("")),
+            Err(ContractError::MissingLegacySourceChain {})
+        ));
+
+        migrate_legacy_swap_datas(&mut storage, "this-chain", Some("source-chain")).unwrap();
+        assert!(get_processed_status(&storage, "source-chain", "tx").is_some());
+        assert!(get_swap_data(&storage, "source-chain", "tx").is_some());
+        assert!(get_processed_status(&storage, "", "tx").is_none());
+    }
+
+    #[test]
+    fn bridge_fee_compute_is_checked() {
+        let fee = BridgeFee {
+            fixed: Uint128::new(10),
+            bps: 100,
+            native_denom: None,
+        };
+        assert_eq!(fee.compute(Uint128::new(1000)).unwrap(), Uint128::new(20));
+
+        let fee = BridgeFee {
+            fixed: Uint128::MAX - Uint128::new(1),
+            bps: 100,
+            native_denom: None,
+        };
+        assert!(fee.compute(Uint128::new(1000)).is_err());
+
+        // a native fee is flat whatever the amount
+        let fee = BridgeFee {
+            fixed: Uint128::new(5),
+            bps: 0,
+            native_denom: Some("uluna".to_string()),
+        };
+        assert_eq!(fee.compute(Uint128::MAX).unwrap(), Uint128::new(5));
+    }
+
+    #[test]
+    fn evm_address_checksum() {
+        // EIP-55 test vectors
+        assert!(is_valid_evm_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        ));
+        assert!(is_valid_evm_address(
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        ));
+        assert!(is_valid_evm_address(
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB"
+        ));
+        assert!(is_valid_evm_address(
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb"
+        ));
+        // single-case addresses carry no checksum
+        assert!(is_valid_evm_address(
+            "0x52908400098527886e0f7030069857d2e4169ee7"
+        ));
+        assert!(is_valid_evm_address(
+            "0x52908400098527886E0F7030069857D2E4169EE7"
+        ));
+
+        assert!(!is_valid_evm_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+        ));
+        assert!(!is_valid_evm_address(
+            "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        ));
+        assert!(!is_valid_evm_address(
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        ));
+        assert!(!is_valid_evm_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"
+        ));
+        assert!(!is_valid_evm_address(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"
+        ));
+    }
+
+    #[test]
+    fn bech32_address_shape() {
+        let address = "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02";
+        assert!(is_valid_bech32_address(address, "cosmos"));
+
+        assert!(!is_valid_bech32_address(address, "osmo"));
+        assert!(!is_valid_bech32_address(&address.to_uppercase(), "cosmos"));
+        // 'b' is outside the bech32 charset
+        assert!(!is_valid_bech32_address(
+            "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6db02",
+            "cosmos"
+        ));
+        assert!(!is_valid_bech32_address(
+            "cosmoshsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02",
+            "cosmos"
+        ));
+        assert!(!is_valid_bech32_address("cosmos1qqqqq", "cosmos"));
+    }
+}
@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use cw721_base::ContractError;
+use monsterra_signing::SigningError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,6 +8,9 @@ pub enum MonsterraNFTError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Signing(#[from] SigningError),
+
     #[error("{0}")]
     CW721(#[from] ContractError),
 
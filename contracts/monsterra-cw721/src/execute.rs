@@ -7,10 +7,12 @@ use cosmwasm_std::{
 use cw721::Cw721Execute;
 use cw721_base::state::TokenInfo;
 use cw721_base::ContractError;
-use sha2::{Digest, Sha256};
+use monsterra_signing::{get_signature_scheme, verify_signature};
 
 use crate::msg::{MintBatchMsg, MintBatchWithSignatureMsg, MintBatchWithSignaturePayload, MintMsg};
-use crate::state::{get_signer, is_admin, is_used_nonce, set_used_nonce, STAKE_OWNERS};
+use crate::state::{
+    get_signer, is_admin, is_used_nonce, set_used_nonce, SIGNATURE_SCHEME, STAKE_OWNERS,
+};
 use crate::Extension;
 
 pub fn mint_batch(
@@ -198,10 +200,7 @@ fn verify_sig(deps: Deps, mint_payload: &MintBatchWithSignaturePayload, signatur
         Err(_) => return false,
     };
     let signer = get_signer(deps.storage);
+    let scheme = get_signature_scheme(deps.storage, &SIGNATURE_SCHEME);
 
-    let hash = Sha256::digest(msg);
-    let result = deps
-        .api
-        .secp256k1_verify(hash.as_ref(), &signature, &signer.0);
-    result.unwrap_or(false)
+    verify_signature(deps.api, &scheme, &msg, &signature, &signer)
 }
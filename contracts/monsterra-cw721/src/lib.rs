@@ -16,8 +16,10 @@ use execute::{internal_mint, mint_batch, mint_batch_with_signature, stake_batch}
 use query::nft_info;
 
 use error::MonsterraNFTError;
+use monsterra_signing::{get_signature_scheme, set_signature_scheme};
 use state::{
     get_base_uri, get_signer, is_admin, is_used_nonce, set_admin, set_base_uri, set_signer,
+    SIGNATURE_SCHEME, SIGNER,
 };
 
 // see: https://docs.opensea.io/docs/metadata-standards
@@ -79,6 +81,17 @@ pub mod entry {
             }
             ExecuteMsg::SetAdmin { user, status } => set_admin(deps.storage, &info, user, status),
             ExecuteMsg::SetSigner { public_key } => set_signer(deps.storage, &info, public_key),
+            ExecuteMsg::SetSignatureScheme { scheme } => {
+                let admin = is_admin(deps.storage, info.sender);
+                let signer = SIGNER.may_load(deps.storage)?;
+                Ok(set_signature_scheme(
+                    deps.storage,
+                    &SIGNATURE_SCHEME,
+                    admin,
+                    scheme,
+                    signer.as_deref(),
+                )?)
+            }
             ExecuteMsg::SetBaseUri { base_uri } => set_base_uri(deps.storage, &info, base_uri),
             ExecuteMsg::InternalMint { msg } => {
                 // only the contract itself can call this
@@ -112,6 +125,9 @@ pub mod entry {
             QueryMsg::IsUsedNonce { nonce } => to_binary(&is_used_nonce(deps.storage, nonce)),
             QueryMsg::IsAdmin { user } => to_binary(&is_admin(deps.storage, user)),
             QueryMsg::GetSigner {} => to_binary(&get_signer(deps.storage)),
+            QueryMsg::GetSignatureScheme {} => {
+                to_binary(&get_signature_scheme(deps.storage, &SIGNATURE_SCHEME))
+            }
             QueryMsg::GetBaseUri {} => to_binary(&get_base_uri(deps.storage)),
             _ => MonsterraNFT::default().query(deps, env, msg.into()),
         }
@@ -7,6 +7,7 @@ use schemars::JsonSchema;
 use cosmwasm_std::Binary;
 use cw721::Expiration;
 
+use crate::state::SignatureScheme;
 use crate::Extension;
 use cw721_base::ExecuteMsg as CW721ExecuteMsg;
 use cw721_base::InstantiateMsg as CW721InstantiateMsg;
@@ -117,6 +118,10 @@ pub enum MonsterraNFTExecuteMsg<T, E> {
         public_key: Binary,
     },
 
+    SetSignatureScheme {
+        scheme: SignatureScheme,
+    },
+
     MintBatch(MintBatchMsg<T>),
 
     StakeBatch {
@@ -193,6 +198,7 @@ impl<T, E> From<MonsterraNFTExecuteMsg<T, E>> for CW721ExecuteMsg<T, E> {
                 token_uri,
                 extension,
             },
+            MonsterraNFTExecuteMsg::Burn { token_id } => CW721ExecuteMsg::Burn { token_id },
             _ => panic!("cannot covert to CW721ExecuteMsg"),
         }
     }
@@ -327,6 +333,9 @@ pub enum MonsterraNFTQueryMsg<Q: JsonSchema> {
     #[returns(Binary)]
     GetSigner {},
 
+    #[returns(SignatureScheme)]
+    GetSignatureScheme {},
+
     #[returns(String)]
     GetBaseUri {},
 }
@@ -2,11 +2,14 @@ use cosmwasm_std::{to_binary, Addr, Binary, MessageInfo, Response, Storage};
 // use `cw_storage_plus` to create ORM-like interface to storage
 // see: https://crates.io/crates/cw-storage-plus
 use cw_storage_plus::{Item, Map};
+pub use monsterra_signing::SignatureScheme;
+use monsterra_signing::{get_signature_scheme, validate_signer};
 
 use crate::error::MonsterraNFTError;
 
 pub const ADMIN: Map<Addr, bool> = Map::new("admin");
 pub const SIGNER: Item<Binary> = Item::new("signer");
+pub const SIGNATURE_SCHEME: Item<SignatureScheme> = Item::new("signature_scheme");
 
 pub const BASE_URI: Item<String> = Item::new("base_uri");
 
@@ -51,6 +54,10 @@ pub fn set_signer(
     if !is_admin(storage, info.sender.clone()) {
         return Err(MonsterraNFTError::Unauthorized {});
     }
+    validate_signer(
+        &get_signature_scheme(storage, &SIGNATURE_SCHEME),
+        &public_key,
+    )?;
 
     let result = SIGNER.save(storage, &public_key);
     match result {
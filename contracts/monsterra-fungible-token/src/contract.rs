@@ -9,7 +9,9 @@ use crate::execute::mint_with_signature;
 use crate::msg::{ExecuteMsg, QueryMsg};
 use crate::state::{
     get_owner, get_signer, is_admin, is_used_nonce, set_admin, set_new_owner, set_signer, OWNER,
+    SIGNATURE_SCHEME, SIGNER,
 };
+use monsterra_signing::{get_signature_scheme, set_signature_scheme};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, CW20Error> {
@@ -42,6 +44,11 @@ pub fn execute(
         ExecuteMsg::TransferOwnerShip { user } => set_new_owner(deps.storage, &info, user),
         ExecuteMsg::SetAdmin { user, status } => set_admin(deps.storage, &info, user, status),
         ExecuteMsg::SetSigner { public_key } => set_signer(deps.storage, &info, public_key),
+        ExecuteMsg::SetSignatureScheme { scheme } => {
+            let admin = is_admin(deps.storage, info.sender);
+            let signer = SIGNER.may_load(deps.storage)?;
+            Ok(set_signature_scheme(deps.storage, &SIGNATURE_SCHEME, admin, scheme, signer.as_deref())?)
+        }
         ExecuteMsg::MintWithSignature { msg, signature } => mint_with_signature(deps, env, &info, msg, signature),
         _ => match cw20_base::contract::execute(deps, env, info, msg.into()) {
             Ok(res) => Ok(res),
@@ -56,6 +63,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetOwner {} => to_binary(&get_owner(deps.storage)),
         QueryMsg::IsAdmin { user } => to_binary(&is_admin(deps.storage, user)),
         QueryMsg::GetSigner {} => to_binary(&get_signer(deps.storage)),
+        QueryMsg::GetSignatureScheme {} => to_binary(&get_signature_scheme(deps.storage, &SIGNATURE_SCHEME)),
         QueryMsg::IsUsedNonce { nonce } => to_binary(&is_used_nonce(deps.storage, nonce)),
         _ => cw20_base::contract::query(deps, _env, msg.into()),
     }
@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use cw20_base::ContractError as CW20Error;
+use monsterra_signing::SigningError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,6 +8,9 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Signing(#[from] SigningError),
+
     #[error("{0}")]
     CW20(#[from] CW20Error),
 
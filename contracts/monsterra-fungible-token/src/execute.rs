@@ -3,10 +3,9 @@ use cosmwasm_std::{
 };
 use cw20_base::state::{BALANCES, TOKEN_INFO};
 use cw20_base::ContractError as CW20Error;
+use monsterra_signing::{get_signature_scheme, verify_signature};
 
-use sha2::{Digest, Sha256};
-
-use crate::state::{is_used_nonce, set_used_nonce};
+use crate::state::{is_used_nonce, set_used_nonce, SIGNATURE_SCHEME};
 use crate::{
     error::ContractError,
     msg::{MintMsg, MintPayload},
@@ -84,13 +83,7 @@ fn verify_sig(deps: Deps, unstake_payload: &MintPayload, signature: Binary) -> b
         Err(_) => return false,
     };
     let signer = get_signer(deps.storage);
+    let scheme = get_signature_scheme(deps.storage, &SIGNATURE_SCHEME);
 
-    let hash = Sha256::digest(msg);
-    let result = deps
-        .api
-        .secp256k1_verify(hash.as_ref(), &signature, &signer.0);
-    match result {
-        Ok(value) => value,
-        Err(_) => false,
-    }
+    verify_signature(deps.api, &scheme, &msg, &signature, &signer)
 }
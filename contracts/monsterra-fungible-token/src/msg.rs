@@ -4,6 +4,8 @@ use cw20::Logo;
 use cw20_base::msg::{ExecuteMsg as CW20ExecuteMsg, QueryMsg as CW20ueryMsg};
 use cw_utils::Expiration;
 
+use crate::state::SignatureScheme;
+
 #[cw_serde]
 pub struct InstantiateMsg {}
 
@@ -102,6 +104,9 @@ pub enum ExecuteMsg {
     SetSigner {
         public_key: Binary,
     },
+    SetSignatureScheme {
+        scheme: SignatureScheme,
+    },
     MintWithSignature {
         msg: MintMsg,
         signature: Binary,
@@ -260,6 +265,8 @@ pub enum QueryMsg {
     IsAdmin { user: Addr },
     #[returns(Binary)]
     GetSigner {},
+    #[returns(SignatureScheme)]
+    GetSignatureScheme {},
     #[returns(bool)]
     IsUsedNonce { nonce: String },
 }
@@ -4,6 +4,8 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{to_binary, Addr, Binary, MessageInfo, Response, Storage, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
+pub use monsterra_signing::SignatureScheme;
+use monsterra_signing::{get_signature_scheme, validate_signer};
 
 use crate::error::ContractError;
 
@@ -18,6 +20,7 @@ pub struct StakeData {
 pub const OWNER: Item<Addr> = Item::new("owner");
 pub const ADMIN: Map<Addr, bool> = Map::new("admin");
 pub const SIGNER: Item<Binary> = Item::new("signer");
+pub const SIGNATURE_SCHEME: Item<SignatureScheme> = Item::new("signature_scheme");
 
 pub const USED_NONCES: Map<String, bool> = Map::new("used_nonces");
 
@@ -77,6 +80,10 @@ pub fn set_signer(
     if !is_admin(storage, info.sender.clone()) {
         return Err(ContractError::Unauthorized {});
     }
+    validate_signer(
+        &get_signature_scheme(storage, &SIGNATURE_SCHEME),
+        &public_key,
+    )?;
 
     let result = SIGNER.save(storage, &public_key);
     match result {
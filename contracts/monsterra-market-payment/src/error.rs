@@ -9,12 +9,33 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("InvalidContractName")]
+    InvalidContractName {},
+
+    #[error("InvalidContractVersion")]
+    InvalidContractVersion {},
+
+    #[error("CannotDowngrade")]
+    CannotDowngrade {},
+
     #[error("Contract Address already added")]
     Added {},
 
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
+    #[error("Contract is neither a CW20 nor a CW721 token")]
+    UnknownTokenStandard {},
+
+    #[error("Invalid fee")]
+    InvalidFee {},
+
+    #[error("Fee change must be scheduled at least {min_delay} seconds ahead")]
+    FeeChangeTooSoon { min_delay: u64 },
+
+    #[error("Fee change not found or already effective")]
+    FeeChangeNotFound {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
@@ -1,14 +1,26 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Addr};
+use cosmwasm_std::{
+    DepsMut, Env, MessageInfo, QuerierWrapper, Response, StdResult, Storage, Addr, Order,
+};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use cw721::{ContractInfoResponse, Cw721QueryMsg, NumTokensResponse};
 
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+use cw_storage_plus::{Bound, Index, MultiIndex};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
-use crate::state::{ContractInfo, GamePaymentContract, PaymentMethod, ContractSupport};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+use crate::state::{
+    ContractInfo, ContractSupport, ContractSupportStatus, GamePaymentContract, PaymentMethod,
+    PaymentMethodStatus, TokenMetadata, DEFAULT_FEE_CHANGE_DELAY, TokenStandard, LEGACY_CONTRACT_SUPPORTS, LEGACY_TOKEN_PAYMENTS,
+    STRING_KEYED_TOKEN_PAYMENTS, string_keyed_payment_method_idx,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:game-payment";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// Fees are expressed in basis points
+const MAX_FEE: u16 = 10000;
 
 impl<'a> GamePaymentContract<'a>
 {
@@ -26,15 +38,119 @@ impl<'a> GamePaymentContract<'a>
             symbol: msg.symbol,
             owner: _info.sender.clone(),
             total_contract_supported: 0,
+            fee_change_delay: msg.fee_change_delay.unwrap_or(DEFAULT_FEE_CHANGE_DELAY),
         };
         self.contract_info.save(deps.storage, &contract_info)?;
         Ok(Response::default())
     }
 
-    pub fn execute(
+    pub fn migrate(
         &self,
         deps: DepsMut,
         _env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response<>, ContractError> {
+        let version = get_contract_version(deps.storage)?;
+        if version.contract != CONTRACT_NAME {
+            return Err(ContractError::InvalidContractName {});
+        }
+
+        let stored_version =
+            Version::parse(&version.version).map_err(|_| ContractError::InvalidContractVersion {})?;
+        let current_version =
+            Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidContractVersion {})?;
+        if stored_version > current_version {
+            return Err(ContractError::CannotDowngrade {});
+        }
+
+        // 0.2.0 replaced boolean statuses and re-keyed the stored records
+        if stored_version < Version::new(0, 2, 0) {
+            // Rewrite records still stored with a boolean status and without
+            // detected metadata, falling back to the stored flag if probing fails
+            let contracts = LEGACY_CONTRACT_SUPPORTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (_, legacy) in contracts {
+                let metadata = detect_token_standard(&deps.querier, &legacy.contract_address)
+                    .unwrap_or(TokenMetadata {
+                        standard: if legacy.is_cw721 {
+                            TokenStandard::Cw721
+                        } else {
+                            TokenStandard::Cw20
+                        },
+                        name: String::new(),
+                        symbol: String::new(),
+                    });
+                let contract = ContractSupport {
+                    contract_address: legacy.contract_address.clone(),
+                    fee: legacy.fee,
+                    is_cw721: metadata.standard == TokenStandard::Cw721,
+                    standard: metadata.standard,
+                    name: metadata.name,
+                    symbol: metadata.symbol,
+                    status: if legacy.status {
+                        ContractSupportStatus::Active
+                    } else {
+                        ContractSupportStatus::Removed
+                    },
+                };
+                self.contract_supports.replace(
+                    deps.storage,
+                    &legacy.contract_address,
+                    Some(&contract),
+                    Some(&contract),
+                )?;
+            }
+
+            let payments = LEGACY_TOKEN_PAYMENTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, legacy) in payments {
+                let payment_method = PaymentMethod {
+                    contract_address: legacy.contract_address,
+                    payment_contract: legacy.payment_contract,
+                    fee: None,
+                    status: if legacy.status {
+                        PaymentMethodStatus::Enabled
+                    } else {
+                        PaymentMethodStatus::Disabled
+                    },
+                };
+                STRING_KEYED_TOKEN_PAYMENTS.save(deps.storage, &key, &payment_method)?;
+            }
+
+            // Move payment methods from the concatenated string key to the
+            // `(contract, payment)` composite key, dropping the old index entries
+            let string_keyed_index: MultiIndex<String, PaymentMethod, String> = MultiIndex::new(
+                string_keyed_payment_method_idx,
+                "token_payments",
+                "method",
+            );
+            let payments = STRING_KEYED_TOKEN_PAYMENTS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, payment_method) in payments {
+                string_keyed_index.remove(deps.storage, key.as_bytes(), &payment_method)?;
+                STRING_KEYED_TOKEN_PAYMENTS.remove(deps.storage, &key);
+                self.token_payments.save(
+                    deps.storage,
+                    (&payment_method.contract_address, &payment_method.payment_contract),
+                    &payment_method,
+                )?;
+            }
+        }
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", version.version)
+            .add_attribute("to_version", CONTRACT_VERSION))
+    }
+
+    pub fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         msg: ExecuteMsg<>,
     ) -> Result<Response<>, ContractError> {
@@ -48,17 +164,37 @@ impl<'a> GamePaymentContract<'a>
                 contract_address,
                 fee,
                 payment_contract,
-                is_cw721,
-            } => self.add_contract_support(deps, info, contract_address, fee, payment_contract, is_cw721),
+            } => self.add_contract_support(deps, info, contract_address, fee, payment_contract),
             ExecuteMsg::UpdateFee {
                 contract_address,
                 fee,
-            } => self.update_fee(deps, info, contract_address, fee),
+            } => self.update_fee(deps, env, info, contract_address, fee),
+            ExecuteMsg::ScheduleFeeChange {
+                contract_address,
+                fee,
+                effective_at,
+            } => self.schedule_fee_change(deps, env, info, contract_address, fee, effective_at),
+            ExecuteMsg::CancelFeeChange {
+                contract_address,
+                payment_contract,
+                effective_at,
+            } => self.cancel_fee_change(deps, env, info, contract_address, payment_contract, effective_at),
             ExecuteMsg::SetPaymentMethod {
                 contract_address,
                 payment_contract,
                 status,
-            } => self.set_payment_method(deps, info, contract_address, payment_contract, status),
+                fee,
+            } => self.set_payment_method(
+                deps,
+                env,
+                info,
+                PaymentMethod {
+                    contract_address,
+                    payment_contract,
+                    status,
+                    fee,
+                },
+            ),
             ExecuteMsg::RemoveContractSupport { contract_address } => self.remove_contract_support(deps, info, contract_address),
         }
     }
@@ -75,24 +211,43 @@ pub trait GamePaymentExecute<>
         contract_address: Addr,
         fee: u16,
         payment_contract: Addr,
-        is_cw721: bool,
     ) -> Result<Response, Self::Err>;
 
     fn update_fee(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         contract_address: Addr,
         fee: u16,
     ) -> Result<Response, Self::Err>;
 
-    fn set_payment_method(
+    fn schedule_fee_change(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         contract_address: Addr,
-        payment_contract: Addr,
-        status: bool,
+        fee: u16,
+        effective_at: u64,
+    ) -> Result<Response, Self::Err>;
+
+    fn cancel_fee_change(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        payment_contract: Option<Addr>,
+        effective_at: u64,
+    ) -> Result<Response, Self::Err>;
+
+    fn set_payment_method(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        payment_method: PaymentMethod,
     ) -> Result<Response<>, Self::Err>;
 
     fn remove_contract_support(
@@ -115,73 +270,181 @@ impl<'a> GamePaymentExecute<> for GamePaymentContract<'a>
         contract_address: Addr,
         fee: u16,
         payment_contract: Addr,
-        is_cw721: bool,
     ) -> Result<Response<>, ContractError> {
+        self.ensure_owner(deps.storage, &info.sender)?;
+        if fee > MAX_FEE {
+            return Err(ContractError::InvalidFee {});
+        }
+        let metadata = detect_token_standard(&deps.querier, &contract_address)
+            .ok_or(ContractError::UnknownTokenStandard {})?;
         let contract = ContractSupport {
             contract_address: contract_address.clone(),
             fee,
-            is_cw721,
-            status: true,
+            is_cw721: metadata.standard == TokenStandard::Cw721,
+            standard: metadata.standard,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            status: ContractSupportStatus::Active,
         };
-        let key = contract_address.clone().to_string() + &payment_contract.clone().to_string();
         let payment_method = PaymentMethod {
             contract_address: contract_address.clone(),
-            status: true,
+            status: PaymentMethodStatus::Enabled,
             payment_contract: payment_contract.clone(),
+            fee: None,
         };
         self.contract_supports
             .update(deps.storage, &contract_address, |old| match old {
                 Some(_) => Err(ContractError::Added {}),
                 None => Ok(contract),
             })?;
-        self.token_payments.update(deps.storage, &key, |old| match old {
-            Some(_) => Err(ContractError::Added {}),
-            None => Ok(payment_method),
-        })?;
+        self.token_payments.update(
+            deps.storage,
+            (&contract_address, &payment_contract),
+            |old| match old {
+                Some(_) => Err(ContractError::Added {}),
+                None => Ok(payment_method),
+            },
+        )?;
 
         Ok(Response::new()
             .add_attribute("action", "add_contract_support")
-            .add_attribute("sender", info.sender))
+            .add_attribute("sender", info.sender)
+            .add_attribute("contract_address", contract_address))
     }
 
     fn update_fee(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         contract_address: Addr,
         fee: u16,
     ) -> Result<Response<>, ContractError> {
-        let mut contract_info = self.contract_supports.load(deps.storage, &contract_address)?;
-        contract_info.fee = fee;
-        self.contract_supports.save(deps.storage, &contract_address, &contract_info)?;
-        // Send message
+        let contract_info = self.contract_info.load(deps.storage)?;
+        let effective_at = env.block.time.seconds() + contract_info.fee_change_delay;
+        self.schedule_fee_change(deps, env, info, contract_address, fee, effective_at)
+    }
+
+    fn schedule_fee_change(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract_address: Addr,
+        fee: u16,
+        effective_at: u64,
+    ) -> Result<Response<>, ContractError> {
+        self.ensure_owner(deps.storage, &info.sender)?;
+        if fee > MAX_FEE {
+            return Err(ContractError::InvalidFee {});
+        }
+        let contract_info = self.contract_info.load(deps.storage)?;
+        let now = env.block.time.seconds();
+        if effective_at < now + contract_info.fee_change_delay {
+            return Err(ContractError::FeeChangeTooSoon {
+                min_delay: contract_info.fee_change_delay,
+            });
+        }
+        self.apply_due_fee_changes(deps.storage, &contract_address, now)?;
+        self.fee_changes
+            .save(deps.storage, (&contract_address, effective_at), &fee)?;
         Ok(Response::new()
-            .add_attribute("action", "update_fee")
-            .add_attribute("sender", info.sender))
+            .add_attribute("action", "schedule_fee_change")
+            .add_attribute("sender", info.sender)
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("fee", fee.to_string())
+            .add_attribute("effective_at", effective_at.to_string()))
     }
 
-    fn set_payment_method(
+    fn cancel_fee_change(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         contract_address: Addr,
-        payment_contract: Addr,
-        status: bool,
+        payment_contract: Option<Addr>,
+        effective_at: u64,
     ) -> Result<Response<>, ContractError> {
-        let _contract_info = self.contract_supports.load(deps.storage, &contract_address)?;
-        let key = contract_address.clone().to_string() + &payment_contract.clone().to_string();
-        let payment_method = PaymentMethod {
+        self.ensure_owner(deps.storage, &info.sender)?;
+        let now = env.block.time.seconds();
+        let is_pending = effective_at > now
+            && match &payment_contract {
+                Some(payment_contract) => self
+                    .payment_fee_changes
+                    .has(deps.storage, (&contract_address, payment_contract, effective_at)),
+                None => self
+                    .fee_changes
+                    .has(deps.storage, (&contract_address, effective_at)),
+            };
+        if !is_pending {
+            return Err(ContractError::FeeChangeNotFound {});
+        }
+        match &payment_contract {
+            Some(payment_contract) => self
+                .payment_fee_changes
+                .remove(deps.storage, (&contract_address, payment_contract, effective_at)),
+            None => self
+                .fee_changes
+                .remove(deps.storage, (&contract_address, effective_at)),
+        }
+        Ok(Response::new()
+            .add_attribute("action", "cancel_fee_change")
+            .add_attribute("sender", info.sender)
+            .add_attribute("contract_address", contract_address)
+            .add_attribute("effective_at", effective_at.to_string()))
+    }
+
+    fn set_payment_method(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        payment_method: PaymentMethod,
+    ) -> Result<Response<>, ContractError> {
+        let PaymentMethod {
             contract_address,
-            status,
             payment_contract,
-        };
-        self.token_payments.update(deps.storage, &key, |old| match old {
-            Some(_) => Err(ContractError::Added {}),
-            None => Ok(payment_method),
-        })?;
-        Ok(Response::new()
+            status,
+            fee,
+        } = payment_method;
+        self.ensure_owner(deps.storage, &info.sender)?;
+        let _contract_info = self.contract_supports.load(deps.storage, &contract_address)?;
+        if fee.unwrap_or(0) > MAX_FEE {
+            return Err(ContractError::InvalidFee {});
+        }
+        let now = env.block.time.seconds();
+        self.apply_due_payment_fee_changes(deps.storage, &contract_address, &payment_contract, now)?;
+        let mut payment_method = self
+            .token_payments
+            .may_load(deps.storage, (&contract_address, &payment_contract))?
+            .unwrap_or(PaymentMethod {
+                contract_address: contract_address.clone(),
+                payment_contract: payment_contract.clone(),
+                status: status.clone(),
+                fee: None,
+            });
+        payment_method.status = status;
+        self.token_payments.save(
+            deps.storage,
+            (&contract_address, &payment_contract),
+            &payment_method,
+        )?;
+
+        let mut response = Response::new()
             .add_attribute("action", "set_payment_method")
-            .add_attribute("sender", info.sender))
+            .add_attribute("sender", info.sender);
+        // Fee overrides go through the same timelock as the contract fee
+        if fee != payment_method.fee {
+            let contract_info = self.contract_info.load(deps.storage)?;
+            let effective_at = now + contract_info.fee_change_delay;
+            self.payment_fee_changes.save(
+                deps.storage,
+                (&contract_address, &payment_contract, effective_at),
+                &fee,
+            )?;
+            response = response.add_attribute("fee_effective_at", effective_at.to_string());
+        }
+        Ok(response)
     }
 
     fn remove_contract_support(
@@ -190,8 +453,9 @@ impl<'a> GamePaymentExecute<> for GamePaymentContract<'a>
         info: MessageInfo,
         contract_address: Addr,
     ) -> Result<Response<>, ContractError> {
+        self.ensure_owner(deps.storage, &info.sender)?;
         let mut contract_info = self.contract_supports.load(deps.storage, &contract_address)?;
-        contract_info.status = false;
+        contract_info.status = ContractSupportStatus::Removed;
         self.contract_supports.save(deps.storage, &contract_address, &contract_info)?;
         Ok(Response::new()
             .add_attribute("action", "remove_contract_support")
@@ -202,5 +466,233 @@ impl<'a> GamePaymentExecute<> for GamePaymentContract<'a>
 // helpers
 impl<'a> GamePaymentContract<'a>
 {
-    
+    fn ensure_owner(&self, storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+        let contract_info = self.contract_info.load(storage)?;
+        if *sender != contract_info.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    // Folds elapsed fee changes into `ContractSupport.fee` and drops them
+    fn apply_due_fee_changes(
+        &self,
+        storage: &mut dyn Storage,
+        contract_address: &Addr,
+        now: u64,
+    ) -> StdResult<()> {
+        let mut contract = self.contract_supports.load(storage, contract_address)?;
+        let due = self
+            .fee_changes
+            .prefix(contract_address)
+            .range(storage, None, Some(Bound::inclusive(now)), Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        if due.is_empty() {
+            return Ok(());
+        }
+        for (effective_at, fee) in due {
+            contract.fee = fee;
+            self.fee_changes.remove(storage, (contract_address, effective_at));
+        }
+        self.contract_supports.save(storage, contract_address, &contract)
+    }
+
+    // Folds elapsed override changes into `PaymentMethod.fee` and drops them
+    fn apply_due_payment_fee_changes(
+        &self,
+        storage: &mut dyn Storage,
+        contract_address: &Addr,
+        payment_contract: &Addr,
+        now: u64,
+    ) -> StdResult<()> {
+        let due = self
+            .payment_fee_changes
+            .prefix((contract_address, payment_contract))
+            .range(storage, None, Some(Bound::inclusive(now)), Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        if due.is_empty() {
+            return Ok(());
+        }
+        let mut payment_method = self
+            .token_payments
+            .may_load(storage, (contract_address, payment_contract))?;
+        for (effective_at, fee) in due {
+            if let Some(payment_method) = payment_method.as_mut() {
+                payment_method.fee = fee;
+            }
+            self.payment_fee_changes
+                .remove(storage, (contract_address, payment_contract, effective_at));
+        }
+        match payment_method {
+            Some(payment_method) => self.token_payments.save(
+                storage,
+                (contract_address, payment_contract),
+                &payment_method,
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+// A CW721 must answer both `ContractInfo` and `NumTokens`, a CW20 must answer `TokenInfo`
+fn detect_token_standard(querier: &QuerierWrapper, contract_address: &Addr) -> Option<TokenMetadata> {
+    let nft_info: StdResult<ContractInfoResponse> =
+        querier.query_wasm_smart(contract_address, &Cw721QueryMsg::ContractInfo {});
+    if let Ok(info) = nft_info {
+        let num_tokens: StdResult<NumTokensResponse> =
+            querier.query_wasm_smart(contract_address, &Cw721QueryMsg::NumTokens {});
+        if num_tokens.is_ok() {
+            return Some(TokenMetadata {
+                standard: TokenStandard::Cw721,
+                name: info.name,
+                symbol: info.symbol,
+            });
+        }
+    }
+    let token_info: StdResult<TokenInfoResponse> =
+        querier.query_wasm_smart(contract_address, &Cw20QueryMsg::TokenInfo {});
+    match token_info {
+        Ok(info) => Some(TokenMetadata {
+            standard: TokenStandard::Cw20,
+            name: info.name,
+            symbol: info.symbol,
+        }),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::QueryMsg;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        from_binary, to_binary, ContractResult, OwnedDeps, SystemResult, Uint128, WasmQuery,
+    };
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        // "token" answers as a cw20, anything else is not a token
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg }
+                if contract_addr == "token"
+                    && *msg == to_binary(&Cw20QueryMsg::TokenInfo {}).unwrap() =>
+            {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&TokenInfoResponse {
+                        name: "Token".to_string(),
+                        symbol: "TKN".to_string(),
+                        decimals: 6,
+                        total_supply: Uint128::zero(),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Ok(ContractResult::Err("unsupported query".to_string())),
+        });
+        GamePaymentContract::default()
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                InstantiateMsg {
+                    name: "payment".to_string(),
+                    symbol: "PAY".to_string(),
+                    fee_change_delay: None,
+                },
+            )
+            .unwrap();
+        deps
+    }
+
+    fn add_support(fee: u16) -> ExecuteMsg {
+        ExecuteMsg::AddContractSupport {
+            contract_address: Addr::unchecked("token"),
+            payment_contract: Addr::unchecked("payment_token"),
+            fee,
+        }
+    }
+
+    #[test]
+    fn contract_support_is_owner_only() {
+        let mut deps = setup();
+        let contract = GamePaymentContract::default();
+
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), add_support(100))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        contract
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), add_support(100))
+            .unwrap();
+
+        let remove = ExecuteMsg::RemoveContractSupport {
+            contract_address: Addr::unchecked("token"),
+        };
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), remove.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        contract
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), remove)
+            .unwrap();
+    }
+
+    #[test]
+    fn contract_support_fee_is_bounded() {
+        let mut deps = setup();
+        let contract = GamePaymentContract::default();
+
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                add_support(MAX_FEE + 1),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFee {});
+
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                add_support(MAX_FEE),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn removed_contract_is_not_supported() {
+        let mut deps = setup();
+        let contract = GamePaymentContract::default();
+        let is_supported = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> bool {
+            let msg = QueryMsg::IsTokenSupport {
+                contract_address: Addr::unchecked("token"),
+                payment_contract: Addr::unchecked("payment_token"),
+            };
+            from_binary(&contract.query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+        };
+
+        contract
+            .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), add_support(100))
+            .unwrap();
+        assert!(is_supported(&deps));
+
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::RemoveContractSupport {
+                    contract_address: Addr::unchecked("token"),
+                },
+            )
+            .unwrap();
+        assert!(!is_supported(&deps));
+    }
 }
@@ -5,7 +5,7 @@ pub mod state;
 mod query;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 pub use crate::state::GamePaymentContract;
 use cosmwasm_std::Empty;
 
@@ -42,6 +42,12 @@ pub mod entry {
         tract.execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        let tract = GamePaymentContract::default();
+        tract.migrate(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         let tract = GamePaymentContract::default();
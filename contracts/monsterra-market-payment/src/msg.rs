@@ -2,12 +2,14 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 
 #[allow(unused_imports)]
-use crate::state::{ContractInfo, ContractSupport};
+use crate::state::{ContractInfo, ContractSupport, PaymentMethod, PaymentMethodStatus};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
+    // Defaults to one day
+    pub fee_change_delay: Option<u64>,
 }
 
 #[cw_serde]
@@ -19,16 +21,29 @@ pub enum ExecuteMsg {
         contract_address: Addr,
         payment_contract: Addr,
         fee: u16,
-        is_cw721: bool,
     },
+    // Schedules the fee at the earliest time allowed by the timelock
     UpdateFee {
         contract_address: Addr,
         fee: u16,
     },
+    ScheduleFeeChange {
+        contract_address: Addr,
+        fee: u16,
+        effective_at: u64,
+    },
+    // Cancels a per-payment override change when `payment_contract` is set
+    CancelFeeChange {
+        contract_address: Addr,
+        payment_contract: Option<Addr>,
+        effective_at: u64,
+    },
+    // Status applies immediately, a changed fee override is scheduled like `UpdateFee`
     SetPaymentMethod {
         contract_address: Addr,
         payment_contract: Addr,
-        status: bool,
+        status: PaymentMethodStatus,
+        fee: Option<u16>,
     },
     RemoveContractSupport {
         contract_address: Addr,
@@ -51,4 +66,57 @@ pub enum QueryMsg {
     },
     #[returns(u16)]
     GetContractFee { contract_address: Addr },
+
+    // Fee charged when `contract_address` items are paid with `payment_contract`
+    #[returns(u16)]
+    GetEffectiveFee {
+        contract_address: Addr,
+        payment_contract: Addr,
+    },
+
+    #[returns(ContractSupportsResponse)]
+    ListSupportedContracts {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PaymentMethodsResponse)]
+    PaymentMethodsFor {
+        contract_address: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PendingFeeChangesResponse)]
+    PendingFeeChanges { contract_address: Addr },
+}
+
+#[cw_serde]
+pub struct ContractSupportsResponse {
+    pub contracts: Vec<ContractSupport>,
+}
+
+#[cw_serde]
+pub struct PaymentMethodsResponse {
+    pub payment_methods: Vec<PaymentMethod>,
+}
+
+#[cw_serde]
+pub struct FeeChange {
+    pub fee: u16,
+    pub effective_at: u64,
+}
+
+#[cw_serde]
+pub struct PaymentFeeChange {
+    pub payment_contract: Addr,
+    pub fee: Option<u16>,
+    pub effective_at: u64,
+}
+
+#[cw_serde]
+pub struct PendingFeeChangesResponse {
+    pub contract_address: Addr,
+    pub changes: Vec<FeeChange>,
+    pub payment_changes: Vec<PaymentFeeChange>,
 }
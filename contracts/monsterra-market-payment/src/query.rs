@@ -1,7 +1,14 @@
-use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult, Addr};
+use cosmwasm_std::{to_binary, Binary, Deps, Env, StdResult, Addr, Order};
+use cw_storage_plus::Bound;
 
-use crate::msg::{QueryMsg};
-use crate::state::{ContractInfo, GamePaymentContract, ContractSupport};
+use crate::msg::{
+    ContractSupportsResponse, FeeChange, PaymentFeeChange, PaymentMethodsResponse, PendingFeeChangesResponse,
+    QueryMsg,
+};
+use crate::state::{ContractInfo, GamePaymentContract, ContractSupport, ContractSupportStatus, PaymentMethodStatus};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 impl<'a> GamePaymentQuery<> for GamePaymentContract<'a>
 {
@@ -9,29 +16,138 @@ impl<'a> GamePaymentQuery<> for GamePaymentContract<'a>
         self.contract_info.load(deps.storage)
     }
 
-    fn contract_support_info(&self, deps: Deps, contract_address: Addr) -> StdResult<ContractSupport> {
-        let info = self.contract_supports.load(deps.storage, &contract_address)?;
+    fn contract_support_info(&self, deps: Deps, env: Env, contract_address: Addr) -> StdResult<ContractSupport> {
+        let mut info = self.contract_supports.load(deps.storage, &contract_address)?;
+        let now = env.block.time.seconds();
+        info.fee = self.fee_at(deps.storage, &contract_address, info.fee, now)?;
         Ok(info)
     }
 
     fn is_token_support(&self, deps: Deps, contract_address: Addr, payment_contract: Addr) -> StdResult<bool> {
-        let key = contract_address.to_string() + &payment_contract.to_string();
-        let info = self.token_payments.load(deps.storage, &key);
+        // A removed contract keeps its payment methods but is no longer supported
+        let supported = match self.contract_supports.may_load(deps.storage, &contract_address)? {
+            Some(contract) => contract.status == ContractSupportStatus::Active,
+            None => false,
+        };
+        if !supported {
+            return Ok(false);
+        }
+        let info = self
+            .token_payments
+            .load(deps.storage, (&contract_address, &payment_contract));
         let result = match info {
-            Ok(info) => info.status,
+            Ok(info) => info.status == PaymentMethodStatus::Enabled,
             Err(_) => false,
         };
         Ok(result)
     }
 
-    fn get_contract_fee(&self, deps: Deps, contract_address: Addr) -> StdResult<u16> {
+    fn get_contract_fee(&self, deps: Deps, env: Env, contract_address: Addr) -> StdResult<u16> {
         let info = self.contract_supports.load(deps.storage, &contract_address);
         let result = match info {
-            Ok(info) => info.fee,
+            Ok(info) => {
+                let now = env.block.time.seconds();
+                self.fee_at(deps.storage, &contract_address, info.fee, now)?
+            }
             Err(_) => 0,
         };
         Ok(result)
     }
+
+    fn get_effective_fee(
+        &self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+        payment_contract: Addr,
+    ) -> StdResult<u16> {
+        let payment_method = self
+            .token_payments
+            .may_load(deps.storage, (&contract_address, &payment_contract))?;
+        let fee = match payment_method {
+            Some(payment_method) => self.payment_fee_at(
+                deps.storage,
+                &contract_address,
+                &payment_contract,
+                payment_method.fee,
+                env.block.time.seconds(),
+            )?,
+            None => None,
+        };
+        match fee {
+            Some(fee) => Ok(fee),
+            None => self.get_contract_fee(deps, env, contract_address),
+        }
+    }
+
+    fn pending_fee_changes(
+        &self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+    ) -> StdResult<PendingFeeChangesResponse> {
+        let now = env.block.time.seconds();
+        let changes = self
+            .fee_changes
+            .prefix(&contract_address)
+            .range(deps.storage, Some(Bound::exclusive(now)), None, Order::Ascending)
+            .map(|item| item.map(|(effective_at, fee)| FeeChange { fee, effective_at }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let payment_changes = self
+            .payment_fee_changes
+            .sub_prefix(&contract_address)
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok(((_, effective_at), _)) if *effective_at <= now))
+            .map(|item| {
+                item.map(|((payment_contract, effective_at), fee)| PaymentFeeChange {
+                    payment_contract,
+                    fee,
+                    effective_at,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(PendingFeeChangesResponse {
+            contract_address,
+            changes,
+            payment_changes,
+        })
+    }
+
+    fn list_supported_contracts(
+        &self,
+        deps: Deps,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<ContractSupportsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let contracts = self
+            .contract_supports
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, contract)| contract))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(ContractSupportsResponse { contracts })
+    }
+
+    fn payment_methods_for(
+        &self,
+        deps: Deps,
+        contract_address: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<PaymentMethodsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let payment_methods = self
+            .token_payments
+            .prefix(&contract_address)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, payment_method)| payment_method))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(PaymentMethodsResponse { payment_methods })
+    }
 }
 
 pub trait GamePaymentQuery<>
@@ -39,6 +155,7 @@ pub trait GamePaymentQuery<>
     fn contract_support_info(
         &self,
         deps: Deps,
+        env: Env,
         contract_address: Addr,
     ) -> StdResult<ContractSupport>;
 
@@ -54,13 +171,44 @@ pub trait GamePaymentQuery<>
     fn get_contract_fee(
         &self,
         deps: Deps,
+        env: Env,
+        contract_address: Addr,
+    ) -> StdResult<u16>;
+
+    fn get_effective_fee(
+        &self,
+        deps: Deps,
+        env: Env,
         contract_address: Addr,
+        payment_contract: Addr,
     ) -> StdResult<u16>;
+
+    fn pending_fee_changes(
+        &self,
+        deps: Deps,
+        env: Env,
+        contract_address: Addr,
+    ) -> StdResult<PendingFeeChangesResponse>;
+
+    fn list_supported_contracts(
+        &self,
+        deps: Deps,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<ContractSupportsResponse>;
+
+    fn payment_methods_for(
+        &self,
+        deps: Deps,
+        contract_address: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<PaymentMethodsResponse>;
 }
 
 impl<'a> GamePaymentContract<'a>
 {
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
             QueryMsg::GamePaymentContractInfo {} => to_binary(&self.contract_info(deps)?),
             
@@ -68,6 +216,7 @@ impl<'a> GamePaymentContract<'a>
                 contract_address,
             } => to_binary(&self.contract_support_info(
                 deps,
+                env,
                 contract_address,
             )?),
             QueryMsg::IsTokenSupport {
@@ -82,6 +231,41 @@ impl<'a> GamePaymentContract<'a>
                 contract_address,
             } => to_binary(&self.get_contract_fee(
                 deps,
+                env,
+                contract_address,
+            )?),
+            QueryMsg::GetEffectiveFee {
+                contract_address,
+                payment_contract,
+            } => to_binary(&self.get_effective_fee(
+                deps,
+                env,
+                contract_address,
+                payment_contract,
+            )?),
+            QueryMsg::ListSupportedContracts {
+                start_after,
+                limit,
+            } => to_binary(&self.list_supported_contracts(
+                deps,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::PaymentMethodsFor {
+                contract_address,
+                start_after,
+                limit,
+            } => to_binary(&self.payment_methods_for(
+                deps,
+                contract_address,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::PendingFeeChanges {
+                contract_address,
+            } => to_binary(&self.pending_fee_changes(
+                deps,
+                env,
                 contract_address,
             )?),
         }
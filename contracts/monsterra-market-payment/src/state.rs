@@ -1,15 +1,19 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 pub struct GamePaymentContract<'a>
 {
     pub contract_info: Item<'a, ContractInfo>,
     pub owner: Item<'a,Addr>,
     pub contract_supports: IndexedMap<'a, &'a Addr, ContractSupport, ContractSupportedIndexes<'a>>,
-    pub token_payments: IndexedMap<'a, &'a str, PaymentMethod, PaymentMethodIndexes<'a>>,
+    pub token_payments: Map<'a, (&'a Addr, &'a Addr), PaymentMethod>,
+    // Scheduled fees keyed by (contract, effective_at in seconds)
+    pub fee_changes: Map<'a, (&'a Addr, u64), u16>,
+    // Scheduled per-payment fee overrides keyed by (contract, payment, effective_at in seconds)
+    pub payment_fee_changes: Map<'a, (&'a Addr, &'a Addr, u64), Option<u16>>,
 }
 
 impl<> Default for GamePaymentContract<'static>
@@ -20,8 +24,9 @@ impl<> Default for GamePaymentContract<'static>
             "owner",
             "contract_supports",
             "contract",
-            "token_payments",
-            "method"
+            "payment_methods",
+            "fee_changes",
+            "payment_fee_changes",
         )
     }
 }
@@ -34,21 +39,56 @@ impl<'a> GamePaymentContract<'a>
         contract_support_keys: &'a str,
         contract: &'a str,
         payments_key: &'a str,
-        key: &'a str,
+        fee_changes: &'a str,
+        payment_fee_changes: &'a str,
     ) -> Self {
         let indexes_contract = ContractSupportedIndexes {
             contract: MultiIndex::new(contract_support_idx, contract_support_keys, contract),
         };
-        let indexes_payment = PaymentMethodIndexes {
-            method: MultiIndex::new(payment_method_idx, payments_key, key),
-        };
         Self {
             contract_info: Item::new(contract_info),
             owner: Item::new(owner),
             contract_supports: IndexedMap::new(contract_support_keys, indexes_contract),
-            token_payments: IndexedMap::new(payments_key, indexes_payment),
+            token_payments: Map::new(payments_key),
+            fee_changes: Map::new(fee_changes),
+            payment_fee_changes: Map::new(payment_fee_changes),
         }
     }
+
+    /// Fee of the latest scheduled change effective at `now`, or `base_fee` if none has elapsed
+    pub fn fee_at(
+        &self,
+        storage: &dyn Storage,
+        contract_address: &Addr,
+        base_fee: u16,
+        now: u64,
+    ) -> StdResult<u16> {
+        let latest = self
+            .fee_changes
+            .prefix(contract_address)
+            .range(storage, None, Some(Bound::inclusive(now)), Order::Descending)
+            .next()
+            .transpose()?;
+        Ok(latest.map(|(_, fee)| fee).unwrap_or(base_fee))
+    }
+
+    /// Fee override of the latest scheduled change effective at `now`, or `base_fee` if none has elapsed
+    pub fn payment_fee_at(
+        &self,
+        storage: &dyn Storage,
+        contract_address: &Addr,
+        payment_contract: &Addr,
+        base_fee: Option<u16>,
+        now: u64,
+    ) -> StdResult<Option<u16>> {
+        let latest = self
+            .payment_fee_changes
+            .prefix((contract_address, payment_contract))
+            .range(storage, None, Some(Bound::inclusive(now)), Order::Descending)
+            .next()
+            .transpose()?;
+        Ok(latest.map(|(_, fee)| fee).unwrap_or(base_fee))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -57,6 +97,15 @@ pub struct ContractInfo {
     pub symbol: String,
     pub total_contract_supported: u32,
     pub owner: Addr,
+    // Minimum notice in seconds before a fee change takes effect
+    #[serde(default = "default_fee_change_delay")]
+    pub fee_change_delay: u64,
+}
+
+pub const DEFAULT_FEE_CHANGE_DELAY: u64 = 86400;
+
+fn default_fee_change_delay() -> u64 {
+    DEFAULT_FEE_CHANGE_DELAY
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -64,16 +113,71 @@ pub struct ContractSupport {
     pub contract_address: Addr,
     pub fee: u16,
     pub is_cw721: bool,
-    pub status: bool,
+    // Detected by querying the contract when it is registered
+    pub standard: TokenStandard,
+    pub name: String,
+    pub symbol: String,
+    pub status: ContractSupportStatus,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PaymentMethod {
     pub contract_address: Addr, 
     pub payment_contract: Addr,
+    pub status: PaymentMethodStatus,
+    // Overrides `ContractSupport.fee` when paying with this token
+    pub fee: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractSupportStatus {
+    Active,
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStandard {
+    Cw20,
+    Cw721,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenMetadata {
+    pub standard: TokenStandard,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethodStatus {
+    Enabled,
+    Disabled,
+}
+
+// Records as stored before the lifecycle enums, only read by `migrate`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyContractSupport {
+    pub contract_address: Addr,
+    pub fee: u16,
+    pub is_cw721: bool,
     pub status: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyPaymentMethod {
+    pub contract_address: Addr,
+    pub payment_contract: Addr,
+    pub status: bool,
+}
+
+pub const LEGACY_CONTRACT_SUPPORTS: Map<&Addr, LegacyContractSupport> = Map::new("contract_supports");
+pub const LEGACY_TOKEN_PAYMENTS: Map<&str, LegacyPaymentMethod> = Map::new("token_payments");
+// Payment methods keyed by the concatenated `contract + payment` string, indexed under "method"
+pub const STRING_KEYED_TOKEN_PAYMENTS: Map<&str, PaymentMethod> = Map::new("token_payments");
+
 pub struct ContractSupportedIndexes<'a>
 {
     // pk goes to second tuple element
@@ -88,24 +192,10 @@ impl<'a> IndexList<ContractSupport> for ContractSupportedIndexes<'a>
     }
 }
 
-pub struct PaymentMethodIndexes<'a>
-{
-    // pk goes to second tuple element
-    pub method: MultiIndex<'a, String, PaymentMethod, String>,
-}
-
-impl<'a> IndexList<PaymentMethod> for PaymentMethodIndexes<'a>
-{
-    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PaymentMethod>> + '_> {
-        let v: Vec<&dyn Index<PaymentMethod>> = vec![&self.method];
-        Box::new(v.into_iter())
-    }
-}
-
 pub fn contract_support_idx<>(d: &ContractSupport) -> Addr {
     d.contract_address.clone()
 }
 
-pub fn payment_method_idx<>(d: &PaymentMethod)-> String {
+pub fn string_keyed_payment_method_idx<>(d: &PaymentMethod)-> String {
     d.contract_address.clone().to_string() + (&d.payment_contract.clone().to_string())
 }
\ No newline at end of file
@@ -9,6 +9,15 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("InvalidContractName")]
+    InvalidContractName {},
+
+    #[error("InvalidContractVersion")]
+    InvalidContractVersion {},
+
+    #[error("CannotDowngrade")]
+    CannotDowngrade {},
+
     #[error("Contract Address already added")]
     Added {},
 
@@ -50,6 +59,12 @@ pub enum ContractError {
 
     #[error("Bid expired")]
     BidExpired {},
+
+    #[error("Address is denied")]
+    UserDenied {},
+
+    #[error("Token is denied")]
+    TokenDenied {},
     
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
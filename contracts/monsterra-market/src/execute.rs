@@ -1,16 +1,20 @@
 use std::ops::{Div, Mul, Sub};
 
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, Timestamp, Uint128,
-    WasmMsg,
+    to_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Order as RangeOrder, Response,
+    StdResult, Timestamp, Uint128, WasmMsg,
 };
 
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::interfaces::{ContractSupportResponse, QueryMsg::*};
-use crate::msg::{ExecuteMsg, InstantiateMsg};
-use crate::state::{Bid, Bundle, ContractInfo, GameMarketContract, Order};
+use crate::interfaces::{ContractSupportResponse, GamePaymentQuerier, QueryMsg::*};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
+use crate::state::{
+    Bid, BidStatus, Bundle, BundleStatus, ContractInfo, GameMarketContract, Order, OrderStatus,
+    LEGACY_BIDS, LEGACY_BUNDLES, LEGACY_ORDERS,
+};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
 
@@ -43,6 +47,116 @@ impl<'a> GameMarketContract<'a> {
         Ok(Response::default())
     }
 
+    pub fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response, ContractError> {
+        let version = get_contract_version(deps.storage)?;
+        if version.contract != CONTRACT_NAME {
+            return Err(ContractError::InvalidContractName {});
+        }
+
+        let stored_version =
+            Version::parse(&version.version).map_err(|_| ContractError::InvalidContractVersion {})?;
+        let current_version =
+            Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidContractVersion {})?;
+        if stored_version > current_version {
+            return Err(ContractError::CannotDowngrade {});
+        }
+
+        // 0.2.0 replaced boolean statuses and re-keyed the stored records
+        if stored_version < Version::new(0, 2, 0) {
+            // Rewrite records still stored with a boolean status. Inactive legacy
+            // records cannot tell a sale from a cancellation, so they become `Closed`.
+            // Passing the record as its own old value rewrites its index entries,
+            // which also backfills the seller, bidder and token indexes.
+            let orders = LEGACY_ORDERS
+                .range(deps.storage, None, None, RangeOrder::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, legacy) in orders {
+                let order = Order {
+                    id: legacy.id,
+                    owner: legacy.owner,
+                    token_address: legacy.token_address,
+                    payment_contract: legacy.payment_contract,
+                    token_id: legacy.token_id,
+                    quantity: legacy.quantity,
+                    price: legacy.price,
+                    is_cw721: legacy.is_cw721,
+                    status: if legacy.status {
+                        OrderStatus::Active
+                    } else {
+                        OrderStatus::Closed
+                    },
+                };
+                self.orders
+                    .replace(deps.storage, &key, Some(&order), Some(&order))?;
+            }
+
+            let bids = LEGACY_BIDS
+                .range(deps.storage, None, None, RangeOrder::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, legacy) in bids {
+                let bid = Bid {
+                    id: legacy.id,
+                    owner: legacy.owner,
+                    token_address: legacy.token_address,
+                    payment_contract: legacy.payment_contract,
+                    token_id: legacy.token_id,
+                    quantity: legacy.quantity,
+                    price: legacy.price,
+                    expired: legacy.expired,
+                    status: if legacy.status {
+                        BidStatus::Active
+                    } else {
+                        BidStatus::Closed
+                    },
+                };
+                self.bids
+                    .replace(deps.storage, &key, Some(&bid), Some(&bid))?;
+            }
+
+            let bundles = LEGACY_BUNDLES
+                .range(deps.storage, None, None, RangeOrder::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, legacy) in bundles {
+                let bundle = Bundle {
+                    id: legacy.id,
+                    owner: legacy.owner,
+                    list_token_address: legacy.list_token_address,
+                    payment_contract: legacy.payment_contract,
+                    list_token_id: legacy.list_token_id,
+                    price: legacy.price,
+                    status: if legacy.status {
+                        BundleStatus::Active
+                    } else {
+                        BundleStatus::Closed
+                    },
+                };
+                self.bundles
+                    .replace(deps.storage, &key, Some(&bundle), Some(&bundle))?;
+            }
+
+            // Backfill the owner index of the escrow records
+            let can_accepts = self
+                .can_accept
+                .range(deps.storage, None, None, RangeOrder::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (key, can_accept) in can_accepts {
+                self.can_accept
+                    .replace(deps.storage, &key, Some(&can_accept), None)?;
+            }
+        }
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", version.version)
+            .add_attribute("to_version", CONTRACT_VERSION))
+    }
+
     pub fn execute(
         &self,
         deps: DepsMut,
@@ -128,6 +242,14 @@ impl<'a> GameMarketContract<'a> {
             ExecuteMsg::UpdateGameMarketPaymentContract {
                 game_market_payment_contract,
             } => self.update_game_market_payment_contract(deps, info, game_market_payment_contract),
+            ExecuteMsg::SetDeniedUser { user, status } => {
+                self.set_denied_user(deps, info, user, status)
+            }
+            ExecuteMsg::SetDeniedToken {
+                token_address,
+                token_id,
+                status,
+            } => self.set_denied_token(deps, info, token_address, token_id, status),
         }
     }
 }
@@ -260,6 +382,23 @@ pub trait GameMarketExecute {
         info: MessageInfo,
         game_market_payment_contract: Addr,
     ) -> Result<Response, Self::Err>;
+
+    fn set_denied_user(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        user: Addr,
+        status: bool,
+    ) -> Result<Response, Self::Err>;
+
+    fn set_denied_token(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_address: Addr,
+        token_id: String,
+        status: bool,
+    ) -> Result<Response, Self::Err>;
 }
 
 impl<'a> GameMarketExecute for GameMarketContract<'a> {
@@ -277,6 +416,8 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         quantity: Uint128,
     ) -> Result<Response, ContractError> {
         let mut contract_info = self.contract_info.load(deps.storage)?;
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_token_allowed(deps.storage, &token_address, &token_id)?;
         let is_payment_token_supported: bool = deps.querier.query_wasm_smart(
             contract_info.game_market_payment_contract.clone(),
             &IsTokenSupport {
@@ -331,7 +472,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
             quantity: quantity.clone(),
             price: price.clone(),
             is_cw721: data_contract_support.is_cw721,
-            status: true,
+            status: OrderStatus::Active,
         };
         contract_info.total_order += 1;
         self.contract_info.save(deps.storage, &contract_info)?;
@@ -385,12 +526,14 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         price: Uint128,
     ) -> Result<Response, ContractError> {
         let mut order = self.orders.load(deps.storage, &order_id)?;
-        if !order.status {
+        if order.status != OrderStatus::Active {
             return Err(ContractError::OrderCanceled {});
         }
         if order.owner != info.sender {
             return Err(ContractError::NotOwner {});
         }
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_token_allowed(deps.storage, &order.token_address, &order.token_id)?;
         if quantity == Uint128::zero() {
             return Err(ContractError::InvalidQuantity {});
         }
@@ -444,21 +587,23 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         quantity: Uint128,
     ) -> Result<Response, ContractError> {
         let mut order = self.orders.load(deps.storage, &order_id)?;
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_user_allowed(deps.storage, &order.owner)?;
+        self.ensure_token_allowed(deps.storage, &order.token_address, &order.token_id)?;
         let contract_info = self.contract_info.load(deps.storage)?;
-        let data_contract_support: ContractSupportResponse = deps.querier.query_wasm_smart(
+        let fee = deps.querier.effective_fee(
             contract_info.game_market_payment_contract.clone(),
-            &ContractSupportInfo {
-                contract_address: order.token_address.clone(),
-            },
+            order.token_address.clone(),
+            order.payment_contract.clone(),
         )?;
-        if !order.status {
+        if order.status != OrderStatus::Active {
             return Err(ContractError::OrderCanceled {});
         }
         if quantity == Uint128::zero() || quantity > order.quantity {
             return Err(ContractError::InvalidQuantity {});
         }
         if quantity == order.quantity {
-            order.status = false;
+            order.status = OrderStatus::Filled;
             order.quantity = Uint128::zero();
         } else {
             order.quantity = Uint128::sub(order.quantity, quantity);
@@ -509,7 +654,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
                     recipient: order.owner.to_string(),
                     amount: caculate_amount(
                         Uint128::mul(order.price, quantity),
-                        data_contract_support.fee,
+                        fee,
                     ),
                 })?,
                 funds: vec![],
@@ -529,7 +674,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         order_id: String,
     ) -> Result<Response, ContractError> {
         let mut order = self.orders.load(deps.storage, &order_id)?;
-        if !order.status {
+        if order.status != OrderStatus::Active {
             return Err(ContractError::OrderCanceled {});
         }
         if order.owner != info.sender {
@@ -537,7 +682,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         }
         let quantity = order.quantity.clone();
         order.quantity = Uint128::zero();
-        order.status = false;
+        order.status = OrderStatus::Cancelled;
         self.orders.save(deps.storage, &order_id, &order)?;
         let mut messages: Vec<CosmosMsg> = vec![];
         if order.is_cw721 {
@@ -586,6 +731,8 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         expired: u64,
     ) -> Result<Response, ContractError> {
         let mut contract_info = self.contract_info.load(deps.storage)?;
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_token_allowed(deps.storage, &token_address, &token_id)?;
         let is_payment_token_supported: bool = deps.querier.query_wasm_smart(
             contract_info.game_market_payment_contract.clone(),
             &IsTokenSupport {
@@ -617,7 +764,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
             token_id: token_id.clone(),
             quantity: Uint128::from(1u128),
             price: price.clone(),
-            status: true,
+            status: BidStatus::Active,
             expired,
         };
         contract_info.total_bid += 1;
@@ -651,12 +798,14 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         expired: u64,
     ) -> Result<Response, ContractError> {
         let mut bid = self.bids.load(deps.storage, &bid_id)?;
-        if !bid.status {
+        if bid.status != BidStatus::Active {
             return Err(ContractError::BidCanceled {});
         }
         if bid.owner != info.sender {
             return Err(ContractError::NotOwner {});
         }
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_token_allowed(deps.storage, &bid.token_address, &bid.token_id)?;
         if price <= Uint128::zero() {
             return Err(ContractError::InvalidPrice {});
         }
@@ -705,21 +854,23 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
     ) -> Result<Response, ContractError> {
         let contract_info = self.contract_info.load(deps.storage)?;
         let mut bid = self.bids.load(deps.storage, &bid_id)?;
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_user_allowed(deps.storage, &bid.owner)?;
+        self.ensure_token_allowed(deps.storage, &bid.token_address, &bid.token_id)?;
         let sender = info.sender.clone();
-        let data_contract_support: ContractSupportResponse = deps.querier.query_wasm_smart(
+        let fee = deps.querier.effective_fee(
             contract_info.game_market_payment_contract.clone(),
-            &ContractSupportInfo {
-                contract_address: bid.token_address.clone(),
-            },
+            bid.token_address.clone(),
+            bid.payment_contract.clone(),
         )?;
         if Timestamp::from_seconds(bid.expired) < env.block.time {
             return Err(ContractError::BidExpired {});
         }
-        if !bid.status {
+        if bid.status != BidStatus::Active {
             return Err(ContractError::BidCanceled {});
         }
         bid.quantity = Uint128::zero();
-        bid.status = false;
+        bid.status = BidStatus::Accepted;
         let mut order_id = String::from("0");
         let mut bundle_id = String::from("0");
         self.bids.save(deps.storage, &bid_id, &bid.clone())?;
@@ -755,7 +906,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
             }
             if result.order_id != String::from("0") {
                 let mut order = self.orders.load(deps.storage, &result.order_id)?;
-                order.status = false;
+                order.status = OrderStatus::Accepted;
                 order.quantity = Uint128::zero();
                 self.orders.save(deps.storage, &order.id, &order)?;
                 self.update_can_accept(
@@ -770,7 +921,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
                 order_id = result.order_id;
             } else if result.bundle_id != String::from("0") {
                 let mut bundle = self.bundles.load(deps.storage, &result.bundle_id)?;
-                bundle.status = false;
+                bundle.status = BundleStatus::Accepted;
                 for (index, _) in bundle.list_token_address.iter().enumerate() {
                     if bundle.list_token_address[index].clone() != bid.token_address.clone()
                         || bundle.list_token_id[index].clone() != bid.token_id.clone()
@@ -811,7 +962,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
             contract_addr: bid.payment_contract.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
                 recipient: info.sender.to_string(),
-                amount: caculate_amount(bid.price, data_contract_support.fee),
+                amount: caculate_amount(bid.price, fee),
             })?,
             funds: vec![],
         }));
@@ -826,19 +977,23 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
     fn cancel_bid(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         bid_id: String,
     ) -> Result<Response, ContractError> {
         let mut bid = self.bids.load(deps.storage, &bid_id)?;
-        if !bid.status {
+        if bid.status != BidStatus::Active {
             return Err(ContractError::BidCanceled {});
         }
         if bid.owner != info.sender {
             return Err(ContractError::NotOwner {});
         }
         bid.quantity = Uint128::zero();
-        bid.status = false;
+        bid.status = if Timestamp::from_seconds(bid.expired) < env.block.time {
+            BidStatus::Expired
+        } else {
+            BidStatus::Cancelled
+        };
         self.bids.save(deps.storage, &bid_id, &bid)?;
         Ok(Response::new()
             .add_messages(vec![CosmosMsg::Wasm(WasmMsg::Execute {
@@ -870,7 +1025,13 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         {
             return Err(ContractError::InvalidNumberItem {});
         }
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
         for (index, _) in list_token_address.iter().enumerate() {
+            self.ensure_token_allowed(
+                deps.storage,
+                &list_token_address[index],
+                &list_token_id[index],
+            )?;
             let is_payment_token_supported: bool = deps.querier.query_wasm_smart(
                 contract_info.game_market_payment_contract.clone(),
                 &IsTokenSupport {
@@ -909,7 +1070,7 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
             list_token_address: list_token_address.clone(),
             list_token_id: list_token_id.clone(),
             price: price.clone(),
-            status: true,
+            status: BundleStatus::Active,
             payment_contract,
         };
         contract_info.total_bundle += 1;
@@ -955,10 +1116,19 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
     ) -> Result<Response, ContractError> {
         let contract_info = self.contract_info.load(deps.storage)?;
         let mut bundle = self.bundles.load(deps.storage, &bundle_id)?;
-        if !bundle.status {
+        if bundle.status != BundleStatus::Active {
             return Err(ContractError::BundleCanceled {});
         }
-        bundle.status = false;
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        self.ensure_user_allowed(deps.storage, &bundle.owner)?;
+        for (index, _) in bundle.list_token_address.iter().enumerate() {
+            self.ensure_token_allowed(
+                deps.storage,
+                &bundle.list_token_address[index],
+                &bundle.list_token_id[index],
+            )?;
+        }
+        bundle.status = BundleStatus::Filled;
         let owner = bundle.owner.clone();
         self.bundles.save(deps.storage, &bundle_id, &bundle)?;
         let mut messages: Vec<CosmosMsg> = vec![];
@@ -1015,13 +1185,13 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         bundle_id: String,
     ) -> Result<Response, ContractError> {
         let mut bundle = self.bundles.load(deps.storage, &bundle_id)?;
-        if !bundle.status {
+        if bundle.status != BundleStatus::Active {
             return Err(ContractError::BundleCanceled {});
         }
         if bundle.owner != info.sender {
             return Err(ContractError::NotOwner {});
         }
-        bundle.status = false;
+        bundle.status = BundleStatus::Cancelled;
         let owner = info.sender.clone();
         self.bundles.save(deps.storage, &bundle_id, &bundle)?;
         let mut messages: Vec<CosmosMsg> = vec![];
@@ -1059,12 +1229,20 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         price: Uint128,
     ) -> Result<Response, ContractError> {
         let mut bundle = self.bundles.load(deps.storage, &bundle_id)?;
-        if !bundle.status {
+        if bundle.status != BundleStatus::Active {
             return Err(ContractError::BundleCanceled {});
         }
         if bundle.owner != info.sender {
             return Err(ContractError::NotOwner {});
         }
+        self.ensure_user_allowed(deps.storage, &info.sender)?;
+        for (token_address, token_id) in bundle
+            .list_token_address
+            .iter()
+            .zip(bundle.list_token_id.iter())
+        {
+            self.ensure_token_allowed(deps.storage, token_address, token_id)?;
+        }
 
         bundle.price = price;
         self.bundles.save(deps.storage, &bundle_id, &bundle)?;
@@ -1098,6 +1276,54 @@ impl<'a> GameMarketExecute for GameMarketContract<'a> {
         self.contract_info.save(deps.storage, &contract_info)?;
         Ok(Response::new().add_attribute("action", "update_game_market_payment_contract"))
     }
+
+    fn set_denied_user(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        user: Addr,
+        status: bool,
+    ) -> Result<Response, ContractError> {
+        let contract_info = self.contract_info.load(deps.storage)?;
+        if info.sender != contract_info.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if status {
+            self.denied_users.save(deps.storage, &user, &true)?;
+        } else {
+            self.denied_users.remove(deps.storage, &user);
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_denied_user")
+            .add_attribute("user", user)
+            .add_attribute("status", status.to_string()))
+    }
+
+    fn set_denied_token(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_address: Addr,
+        token_id: String,
+        status: bool,
+    ) -> Result<Response, ContractError> {
+        let contract_info = self.contract_info.load(deps.storage)?;
+        if info.sender != contract_info.owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if status {
+            self.denied_tokens
+                .save(deps.storage, (&token_address, &token_id), &true)?;
+        } else {
+            self.denied_tokens
+                .remove(deps.storage, (&token_address, &token_id));
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_denied_token")
+            .add_attribute("token_address", token_address)
+            .add_attribute("token_id", token_id)
+            .add_attribute("status", status.to_string()))
+    }
 }
 
 fn caculate_amount(amount: Uint128, fee: u16) -> Uint128 {
@@ -20,6 +20,12 @@ pub enum QueryMsg {
         contract_address: Addr,
         payment_contract: Addr,
     },
+
+    #[returns(u16)]
+    GetEffectiveFee {
+        contract_address: Addr,
+        payment_contract: Addr,
+    },
 }
 
 pub enum ExecuteMsg {
@@ -35,7 +41,22 @@ pub struct ContractSupportResponse {
     pub contract_address: Addr,
     pub fee: u16,
     pub is_cw721: bool,
-    pub status: bool,
+    pub standard: TokenStandard,
+    pub name: String,
+    pub symbol: String,
+    pub status: ContractSupportStatus,
+}
+
+#[cw_serde]
+pub enum TokenStandard {
+    Cw20,
+    Cw721,
+}
+
+#[cw_serde]
+pub enum ContractSupportStatus {
+    Active,
+    Removed,
 }
 
 #[cw_serde]
@@ -57,6 +78,12 @@ pub trait GamePaymentQuerier {
         contract_address: Addr,
         payment_contract: Addr,
     ) -> StdResult<bool>;
+    fn effective_fee(
+        &self,
+        feed_address: Addr,
+        contract_address: Addr,
+        payment_contract: Addr,
+    ) -> StdResult<u16>;
 }
 
 impl<'a> GamePaymentQuerier for QuerierWrapper<'a> {
@@ -85,6 +112,21 @@ impl<'a> GamePaymentQuerier for QuerierWrapper<'a> {
             },
         )
     }
+
+    fn effective_fee(
+        &self,
+        feed_address: Addr,
+        contract_address: Addr,
+        payment_contract: Addr,
+    ) -> StdResult<u16> {
+        self.query_wasm_smart(
+            feed_address,
+            &QueryMsg::GetEffectiveFee {
+                contract_address,
+                payment_contract,
+            },
+        )
+    }
 }
 
 pub trait GameMarketExecute {
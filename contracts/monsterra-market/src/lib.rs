@@ -7,7 +7,7 @@ pub mod interfaces;
 
 pub use crate::interfaces::{QueryMsg as QueryMsgWrapper, GamePaymentQuerier};
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 pub use crate::state::GameMarketContract;
 use cosmwasm_std::Empty;
 
@@ -44,6 +44,12 @@ pub mod entry {
         tract.execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        let tract = GameMarketContract::default();
+        tract.migrate(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         let tract = GameMarketContract::default();
@@ -79,6 +79,15 @@ pub enum ExecuteMsg {
     UpdateGameMarketPaymentContract {
         game_market_payment_contract: Addr,
     },
+    SetDeniedUser {
+        user: Addr,
+        status: bool,
+    },
+    SetDeniedToken {
+        token_address: Addr,
+        token_id: String,
+        status: bool,
+    },
 }
 
 #[cw_serde]
@@ -105,4 +114,55 @@ pub enum QueryMsg {
         contract_address: Addr,
         payment_contract: Addr,
     },
+
+    #[returns(OrdersResponse)]
+    OrdersBySeller {
+        seller: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(BidsResponse)]
+    BidsByBidder {
+        bidder: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(BidsResponse)]
+    BidsOnToken {
+        token_address: Addr,
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Active bids on NFTs the owner has escrowed in an order or bundle, followed by
+    // those on the NFTs it holds in `token_address` when one is given
+    #[returns(BidsResponse)]
+    BidsReceived {
+        owner: Addr,
+        token_address: Option<Addr>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(bool)]
+    IsDeniedUser { user: Addr },
+
+    #[returns(bool)]
+    IsDeniedToken {
+        token_address: Addr,
+        token_id: String,
+    },
+}
+
+#[cw_serde]
+pub struct OrdersResponse {
+    pub orders: Vec<Order>,
+}
+
+#[cw_serde]
+pub struct BidsResponse {
+    pub bids: Vec<Bid>,
 }
@@ -1,8 +1,13 @@
-use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{to_binary, Addr, Binary, Deps, Env, Order as RangeOrder, StdResult, Timestamp};
+use cw721::{Cw721QueryMsg, TokensResponse};
+use cw_storage_plus::Bound;
 
 use crate::interfaces::{ContractSupportResponse, QueryMsg::*};
-use crate::msg::QueryMsg;
-use crate::state::{Bid, Bundle, ContractInfo, GameMarketContract, Order};
+use crate::msg::{BidsResponse, OrdersResponse, QueryMsg};
+use crate::state::{Bid, BidStatus, Bundle, ContractInfo, GameMarketContract, Order};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 impl<'a> GameMarketQuery for GameMarketContract<'a> {
     fn contract_info(&self, deps: Deps) -> StdResult<ContractInfo> {
@@ -19,9 +24,200 @@ impl<'a> GameMarketQuery for GameMarketContract<'a> {
         Ok(info)
     }
 
-    fn bid_info(&self, deps: Deps, bid_id: String) -> StdResult<Bid> {
+    fn bid_info(&self, deps: Deps, env: Env, bid_id: String) -> StdResult<Bid> {
         let info = self.bids.load(deps.storage, &bid_id)?;
-        Ok(info)
+        Ok(with_effective_status(info, &env))
+    }
+
+    fn orders_by_seller(
+        &self,
+        deps: Deps,
+        seller: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OrdersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let orders = self
+            .orders
+            .idx
+            .seller
+            .prefix(seller)
+            .range(deps.storage, start, None, RangeOrder::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, order)| order))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(OrdersResponse { orders })
+    }
+
+    fn bids_by_bidder(
+        &self,
+        deps: Deps,
+        env: Env,
+        bidder: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let bids = self
+            .bids
+            .idx
+            .bidder
+            .prefix(bidder)
+            .range(deps.storage, start, None, RangeOrder::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, bid)| with_effective_status(bid, &env)))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BidsResponse { bids })
+    }
+
+    fn bids_on_token(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_address: Addr,
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let bids = self
+            .bids
+            .idx
+            .token
+            .prefix((token_address, token_id))
+            .range(deps.storage, start, None, RangeOrder::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, bid)| with_effective_status(bid, &env)))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BidsResponse { bids })
+    }
+
+    fn bids_received(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: Addr,
+        token_address: Option<Addr>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let cursor = match start_after {
+            Some(bid_id) => Some(self.bids.load(deps.storage, &bid_id)?),
+            None => None,
+        };
+        // Escrowed tokens are listed first, a cursor on any other token is past them
+        let from_escrow = match &cursor {
+            Some(bid) => {
+                let key = bid.token_address.to_string() + &bid.token_id + owner.as_str();
+                match self.can_accept.may_load(deps.storage, &key)? {
+                    Some(can_accept) => can_accept.status,
+                    None => false,
+                }
+            }
+            None => true,
+        };
+        let mut bids: Vec<Bid> = vec![];
+
+        if from_escrow {
+            // Resume from the escrow record of the token the last bid was placed on
+            let start = cursor.as_ref().map(|bid| {
+                Bound::inclusive(bid.token_address.to_string() + &bid.token_id + owner.as_str())
+            });
+            for item in self
+                .can_accept
+                .idx
+                .owner
+                .prefix(owner.clone())
+                .range(deps.storage, start, None, RangeOrder::Ascending)
+            {
+                let (_, can_accept) = item?;
+                if !can_accept.status {
+                    continue;
+                }
+                if self.push_active_bids(
+                    deps,
+                    &env,
+                    (&can_accept.token_address, &can_accept.token_id),
+                    cursor.as_ref(),
+                    &mut bids,
+                    limit,
+                )? {
+                    return Ok(BidsResponse { bids });
+                }
+            }
+        }
+
+        // Then the tokens the owner holds in `token_address`, in token id order
+        let token_address = match token_address {
+            Some(value) => value,
+            None => return Ok(BidsResponse { bids }),
+        };
+        let mut token_start = None;
+        if let Some(bid) = cursor.as_ref().filter(|_| !from_escrow) {
+            if bid.token_address != token_address {
+                return Ok(BidsResponse { bids });
+            }
+            if self.push_active_bids(
+                deps,
+                &env,
+                (&bid.token_address, &bid.token_id),
+                Some(bid),
+                &mut bids,
+                limit,
+            )? {
+                return Ok(BidsResponse { bids });
+            }
+            token_start = Some(bid.token_id.clone());
+        }
+        loop {
+            let page: TokensResponse = deps.querier.query_wasm_smart(
+                token_address.to_string(),
+                &Cw721QueryMsg::Tokens {
+                    owner: owner.to_string(),
+                    start_after: token_start,
+                    limit: Some(MAX_LIMIT),
+                },
+            )?;
+            for token_id in page.tokens.iter() {
+                if self.push_active_bids(
+                    deps,
+                    &env,
+                    (&token_address, token_id),
+                    None,
+                    &mut bids,
+                    limit,
+                )? {
+                    return Ok(BidsResponse { bids });
+                }
+            }
+            token_start = match page.tokens.last() {
+                Some(last) => Some(last.clone()),
+                None => return Ok(BidsResponse { bids }),
+            };
+        }
+    }
+
+    fn is_denied_user(&self, deps: Deps, user: Addr) -> StdResult<bool> {
+        Ok(self
+            .denied_users
+            .may_load(deps.storage, &user)?
+            .unwrap_or(false))
+    }
+
+    fn is_denied_token(
+        &self,
+        deps: Deps,
+        token_address: Addr,
+        token_id: String,
+    ) -> StdResult<bool> {
+        Ok(self
+            .denied_tokens
+            .may_load(deps.storage, (&token_address, &token_id))?
+            .unwrap_or(false))
     }
 
     fn contract_support_info(&self, deps: Deps, contract_address: Addr) -> StdResult<ContractSupportResponse> {
@@ -54,7 +250,49 @@ impl<'a> GameMarketQuery for GameMarketContract<'a> {
 pub trait GameMarketQuery {
     fn order_info(&self, deps: Deps, order_id: String) -> StdResult<Order>;
 
-    fn bid_info(&self, deps: Deps, bid_id: String) -> StdResult<Bid>;
+    fn bid_info(&self, deps: Deps, env: Env, bid_id: String) -> StdResult<Bid>;
+
+    fn orders_by_seller(
+        &self,
+        deps: Deps,
+        seller: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OrdersResponse>;
+
+    fn bids_by_bidder(
+        &self,
+        deps: Deps,
+        env: Env,
+        bidder: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse>;
+
+    fn bids_on_token(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_address: Addr,
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse>;
+
+    fn bids_received(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: Addr,
+        token_address: Option<Addr>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse>;
+
+    fn is_denied_user(&self, deps: Deps, user: Addr) -> StdResult<bool>;
+
+    fn is_denied_token(&self, deps: Deps, token_address: Addr, token_id: String)
+        -> StdResult<bool>;
 
     fn contract_info(&self, deps: Deps) -> StdResult<ContractInfo>;
 
@@ -70,12 +308,12 @@ pub trait GameMarketQuery {
 }
 
 impl<'a> GameMarketContract<'a> {
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
             QueryMsg::ContractInfo {} => to_binary(&self.contract_info(deps)?),
 
             QueryMsg::OrderInfo { order_id } => to_binary(&self.order_info(deps, order_id)?),
-            QueryMsg::BidInfo { bid_id } => to_binary(&self.bid_info(deps, bid_id)?),
+            QueryMsg::BidInfo { bid_id } => to_binary(&self.bid_info(deps, env, bid_id)?),
             QueryMsg::BundleInfo { bundle_id } => to_binary(&self.bundle_info(deps, bundle_id)?),
             QueryMsg::ContractSupportInfo { contract_address } => {
                 to_binary(&self.contract_support_info(deps, contract_address)?)
@@ -84,6 +322,188 @@ impl<'a> GameMarketContract<'a> {
                 contract_address,
                 payment_contract,
             } => to_binary(&self.is_token_support(deps, contract_address, payment_contract)?),
+            QueryMsg::OrdersBySeller {
+                seller,
+                start_after,
+                limit,
+            } => to_binary(&self.orders_by_seller(deps, seller, start_after, limit)?),
+            QueryMsg::BidsByBidder {
+                bidder,
+                start_after,
+                limit,
+            } => to_binary(&self.bids_by_bidder(deps, env, bidder, start_after, limit)?),
+            QueryMsg::BidsOnToken {
+                token_address,
+                token_id,
+                start_after,
+                limit,
+            } => to_binary(&self.bids_on_token(
+                deps,
+                env,
+                token_address,
+                token_id,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::BidsReceived {
+                owner,
+                token_address,
+                start_after,
+                limit,
+            } => to_binary(&self.bids_received(
+                deps,
+                env,
+                owner,
+                token_address,
+                start_after,
+                limit,
+            )?),
+            QueryMsg::IsDeniedUser { user } => to_binary(&self.is_denied_user(deps, user)?),
+            QueryMsg::IsDeniedToken {
+                token_address,
+                token_id,
+            } => to_binary(&self.is_denied_token(deps, token_address, token_id)?),
         }
     }
 }
+
+impl<'a> GameMarketContract<'a> {
+    // Appends the active bids on `token`, after the cursor bid when it is on the same
+    // token. Returns whether `bids` reached `limit`.
+    fn push_active_bids(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token: (&Addr, &String),
+        cursor: Option<&Bid>,
+        bids: &mut Vec<Bid>,
+        limit: usize,
+    ) -> StdResult<bool> {
+        let start = match cursor {
+            Some(bid) if bid.token_address == *token.0 && bid.token_id == *token.1 => {
+                Some(Bound::exclusive(bid.id.clone()))
+            }
+            _ => None,
+        };
+        for item in self
+            .bids
+            .idx
+            .token
+            .prefix((token.0.clone(), token.1.clone()))
+            .range(deps.storage, start, None, RangeOrder::Ascending)
+        {
+            let (_, bid) = item?;
+            let bid = with_effective_status(bid, env);
+            if bid.status != BidStatus::Active {
+                continue;
+            }
+            bids.push(bid);
+            if bids.len() == limit {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+// An active bid past its expiry can no longer be accepted
+fn with_effective_status(mut bid: Bid, env: &Env) -> Bid {
+    if bid.status == BidStatus::Active && Timestamp::from_seconds(bid.expired) < env.block.time {
+        bid.status = BidStatus::Expired;
+    }
+    bid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{from_binary, from_slice, ContractResult, SystemResult, Uint128, WasmQuery};
+
+    fn bid(id: &str, token_id: &str, expired: u64) -> Bid {
+        Bid {
+            id: id.to_string(),
+            owner: Addr::unchecked("bidder"),
+            token_address: Addr::unchecked("nft"),
+            payment_contract: Addr::unchecked("payment"),
+            token_id: token_id.to_string(),
+            quantity: Uint128::new(1),
+            price: Uint128::new(100),
+            expired,
+            status: BidStatus::Active,
+        }
+    }
+
+    fn bid_ids(response: BidsResponse) -> Vec<String> {
+        response.bids.into_iter().map(|bid| bid.id).collect()
+    }
+
+    #[test]
+    fn bids_received_covers_escrowed_and_held_tokens() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let contract = GameMarketContract::default();
+        let owner = Addr::unchecked("owner");
+        let nft = Addr::unchecked("nft");
+        let open = env.block.time.seconds() + 100;
+
+        // "1" and "2" are held by the owner, "3" is escrowed in an order, "9" is someone else's
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "nft" => {
+                let tokens = match from_slice(msg).unwrap() {
+                    Cw721QueryMsg::Tokens {
+                        owner, start_after, ..
+                    } if owner == "owner" => ["1", "2"]
+                        .iter()
+                        .map(|id| id.to_string())
+                        .filter(|id| start_after.is_none() || Some(id) > start_after.as_ref())
+                        .collect(),
+                    _ => vec![],
+                };
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&TokensResponse { tokens }).unwrap(),
+                ))
+            }
+            _ => panic!("unexpected query"),
+        });
+        contract.update_can_accept(
+            &mut deps.storage,
+            true,
+            &nft,
+            &"3".to_string(),
+            &owner,
+            &"".to_string(),
+            &"order".to_string(),
+        );
+        for bid in [
+            bid("b1", "1", open),
+            bid("b2", "3", open),
+            bid("b3", "2", 1),
+            bid("b4", "9", open),
+            bid("b5", "2", open),
+        ] {
+            contract.bids.save(&mut deps.storage, &bid.id.clone(), &bid).unwrap();
+        }
+
+        let received = |start_after: Option<&str>, limit: Option<u32>, token_address| {
+            let msg = QueryMsg::BidsReceived {
+                owner: owner.clone(),
+                token_address,
+                start_after: start_after.map(|id| id.to_string()),
+                limit,
+            };
+            bid_ids(from_binary(&contract.query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap())
+        };
+
+        // escrow only without a collection
+        assert_eq!(received(None, None, None), vec!["b2"]);
+        // escrowed first, then held, skipping expired bids and tokens of others
+        assert_eq!(received(None, None, Some(nft.clone())), vec!["b2", "b1", "b5"]);
+
+        // paging walks from the escrow phase into the held tokens
+        assert_eq!(received(None, Some(1), Some(nft.clone())), vec!["b2"]);
+        assert_eq!(received(Some("b2"), Some(1), Some(nft.clone())), vec!["b1"]);
+        assert_eq!(received(Some("b1"), Some(1), Some(nft.clone())), vec!["b5"]);
+        assert!(received(Some("b5"), Some(1), Some(nft)).is_empty());
+    }
+}
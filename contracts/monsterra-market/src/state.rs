@@ -1,7 +1,7 @@
 use crate::error::ContractError;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Storage, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 pub struct GameMarketContract<'a> {
     pub contract_info: Item<'a, ContractInfo>,
@@ -10,59 +10,68 @@ pub struct GameMarketContract<'a> {
     pub bids: IndexedMap<'a, &'a str, Bid, BidIndexes<'a>>,
     pub bundles: IndexedMap<'a, &'a str, Bundle, BundleIndexes<'a>>,
     pub can_accept: IndexedMap<'a, &'a str, CanAccept, CanAcceptIndexes<'a>>,
+    pub denied_users: Map<'a, &'a Addr, bool>,
+    pub denied_tokens: Map<'a, (&'a Addr, &'a str), bool>,
 }
 
 impl Default for GameMarketContract<'static> {
     fn default() -> Self {
-        Self::new(
-            "contract_info",
-            "owner",
-            "orders_key",
-            "order",
-            "bids_key",
-            "bid",
-            "bundles_key",
-            "bundle",
-            "can_accept_key",
-            "can_accept",
-        )
-    }
-}
-
-impl<'a> GameMarketContract<'a> {
-    fn new(
-        contract_info: &'a str,
-        owner: &'a str,
-        orders_key: &'a str,
-        order: &'a str,
-        bids_key: &'a str,
-        bid: &'a str,
-        bundles_key: &'a str,
-        bundle: &'a str,
-        can_accept_key: &'a str,
-        can_accept: &'a str,
-    ) -> Self {
         let indexes_order = OrderIndexes {
-            order_id: MultiIndex::new(order_idx, orders_key, order),
+            order_id: MultiIndex::new(order_idx, "orders_key", "order"),
+            seller: MultiIndex::new(order_seller_idx, "orders_key", "order_seller"),
         };
         let indexes_bid = BidIndexes {
-            bid_id: MultiIndex::new(bid_idx, bids_key, bid),
+            bid_id: MultiIndex::new(bid_idx, "bids_key", "bid"),
+            bidder: MultiIndex::new(bid_bidder_idx, "bids_key", "bid_bidder"),
+            token: MultiIndex::new(bid_token_idx, "bids_key", "bid_token"),
         };
         let indexes_bundle = BundleIndexes {
-            bundle_id: MultiIndex::new(bundle_idx, bundles_key, bundle),
+            bundle_id: MultiIndex::new(bundle_idx, "bundles_key", "bundle"),
         };
         let indexes_can_accept = CanAcceptIndexes {
-            key: MultiIndex::new(can_accept_idx, can_accept_key, can_accept),
+            key: MultiIndex::new(can_accept_idx, "can_accept_key", "can_accept"),
+            owner: MultiIndex::new(can_accept_owner_idx, "can_accept_key", "can_accept_owner"),
         };
         Self {
-            contract_info: Item::new(contract_info),
-            owner: Item::new(owner),
-            orders: IndexedMap::new(orders_key, indexes_order),
-            bids: IndexedMap::new(bids_key, indexes_bid),
-            bundles: IndexedMap::new(bundles_key, indexes_bundle),
-            can_accept: IndexedMap::new(can_accept_key, indexes_can_accept),
+            contract_info: Item::new("contract_info"),
+            owner: Item::new("owner"),
+            orders: IndexedMap::new("orders_key", indexes_order),
+            bids: IndexedMap::new("bids_key", indexes_bid),
+            bundles: IndexedMap::new("bundles_key", indexes_bundle),
+            can_accept: IndexedMap::new("can_accept_key", indexes_can_accept),
+            denied_users: Map::new("denied_users"),
+            denied_tokens: Map::new("denied_tokens"),
         }
     }
+}
+
+impl<'a> GameMarketContract<'a> {
+    pub fn ensure_user_allowed(
+        &self,
+        storage: &dyn Storage,
+        user: &Addr,
+    ) -> Result<(), ContractError> {
+        if self.denied_users.may_load(storage, user)?.unwrap_or(false) {
+            return Err(ContractError::UserDenied {});
+        }
+        Ok(())
+    }
+
+    pub fn ensure_token_allowed(
+        &self,
+        storage: &dyn Storage,
+        token_address: &Addr,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if self
+            .denied_tokens
+            .may_load(storage, (token_address, token_id))?
+            .unwrap_or(false)
+        {
+            return Err(ContractError::TokenDenied {});
+        }
+        Ok(())
+    }
 
     pub fn update_can_accept(
         &self,
@@ -137,7 +146,7 @@ pub struct Order {
     pub quantity: Uint128,
     pub price: Uint128,
     pub is_cw721: bool,
-    pub status: bool,
+    pub status: OrderStatus,
 }
 
 #[cw_serde]
@@ -150,11 +159,82 @@ pub struct Bid {
     pub quantity: Uint128,
     pub price: Uint128,
     pub expired: u64,
-    pub status: bool,
+    pub status: BidStatus,
 }
 
 #[cw_serde]
 pub struct Bundle {
+    pub id: String,
+    pub owner: Addr,
+    pub list_token_address: Vec<Addr>,
+    pub payment_contract: Addr,
+    pub list_token_id: Vec<String>,
+    pub price: Uint128,
+    pub status: BundleStatus,
+}
+
+/// Lifecycle of an order. `Closed` is only set by the migration for orders
+/// that were already inactive before lifecycle tracking was introduced.
+#[cw_serde]
+pub enum OrderStatus {
+    Active,
+    Filled,
+    Cancelled,
+    /// The escrowed NFT was sold through an accepted bid
+    Accepted,
+    Closed,
+}
+
+/// Lifecycle of a bid. `Expired` is recorded when an expired bid is refunded;
+/// queries also report it for active bids past their expiry.
+#[cw_serde]
+pub enum BidStatus {
+    Active,
+    Accepted,
+    Cancelled,
+    Expired,
+    Closed,
+}
+
+/// Lifecycle of a bundle, see `OrderStatus`.
+#[cw_serde]
+pub enum BundleStatus {
+    Active,
+    Filled,
+    Cancelled,
+    Accepted,
+    Closed,
+}
+
+// Records as stored before the lifecycle enums, only read by `migrate`
+#[cw_serde]
+pub struct LegacyOrder {
+    pub id: String,
+    pub owner: Addr,
+    pub token_address: Addr,
+    pub payment_contract: Addr,
+    pub token_id: String,
+    pub quantity: Uint128,
+    pub price: Uint128,
+    pub is_cw721: bool,
+    pub status: bool,
+}
+
+#[cw_serde]
+pub struct LegacyBid {
+    pub id: String,
+    pub owner: Addr,
+    pub token_address: Addr,
+    pub payment_contract: Addr,
+    pub token_id: String,
+    pub quantity: Uint128,
+    pub price: Uint128,
+    pub expired: u64,
+    pub status: bool,
+}
+
+#[cw_serde]
+pub struct LegacyBundle {
     pub id: String,
     pub owner: Addr,
     pub list_token_address: Vec<Addr>,
@@ -164,14 +244,19 @@ pub struct Bundle {
     pub status: bool,
 }
 
+pub const LEGACY_ORDERS: Map<&str, LegacyOrder> = Map::new("orders_key");
+pub const LEGACY_BIDS: Map<&str, LegacyBid> = Map::new("bids_key");
+pub const LEGACY_BUNDLES: Map<&str, LegacyBundle> = Map::new("bundles_key");
+
 pub struct OrderIndexes<'a> {
     // pk goes to second tuple element
     pub order_id: MultiIndex<'a, String, Order, String>,
+    pub seller: MultiIndex<'a, Addr, Order, String>,
 }
 
 impl<'a> IndexList<Order> for OrderIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Order>> + '_> {
-        let v: Vec<&dyn Index<Order>> = vec![&self.order_id];
+        let v: Vec<&dyn Index<Order>> = vec![&self.order_id, &self.seller];
         Box::new(v.into_iter())
     }
 }
@@ -179,11 +264,13 @@ impl<'a> IndexList<Order> for OrderIndexes<'a> {
 pub struct BidIndexes<'a> {
     // pk goes to second tuple element
     pub bid_id: MultiIndex<'a, String, Bid, String>,
+    pub bidder: MultiIndex<'a, Addr, Bid, String>,
+    pub token: MultiIndex<'a, (Addr, String), Bid, String>,
 }
 
 impl<'a> IndexList<Bid> for BidIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bid>> + '_> {
-        let v: Vec<&dyn Index<Bid>> = vec![&self.bid_id];
+        let v: Vec<&dyn Index<Bid>> = vec![&self.bid_id, &self.bidder, &self.token];
         Box::new(v.into_iter())
     }
 }
@@ -203,11 +290,12 @@ impl<'a> IndexList<Bundle> for BundleIndexes<'a> {
 pub struct CanAcceptIndexes<'a> {
     // pk goes to second tuple element
     pub key: MultiIndex<'a, String, CanAccept, String>,
+    pub owner: MultiIndex<'a, Addr, CanAccept, String>,
 }
 
 impl<'a> IndexList<CanAccept> for CanAcceptIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CanAccept>> + '_> {
-        let v: Vec<&dyn Index<CanAccept>> = vec![&self.key];
+        let v: Vec<&dyn Index<CanAccept>> = vec![&self.key, &self.owner];
         Box::new(v.into_iter())
     }
 }
@@ -216,14 +304,30 @@ pub fn can_accept_idx(d: &CanAccept) -> String {
     d.token_address.clone().to_string() + (&d.token_id.clone()) + (&d.owner.clone().to_string())
 }
 
+pub fn can_accept_owner_idx(d: &CanAccept) -> Addr {
+    d.owner.clone()
+}
+
 pub fn order_idx(d: &Order) -> String {
     d.id.clone()
 }
 
+pub fn order_seller_idx(d: &Order) -> Addr {
+    d.owner.clone()
+}
+
 pub fn bid_idx(d: &Bid) -> String {
     d.id.clone()
 }
 
+pub fn bid_bidder_idx(d: &Bid) -> Addr {
+    d.owner.clone()
+}
+
+pub fn bid_token_idx(d: &Bid) -> (Addr, String) {
+    (d.token_address.clone(), d.token_id.clone())
+}
+
 pub fn bundle_idx(d: &Bundle) -> String {
     d.id.clone()
 }
@@ -3,19 +3,25 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 use crate::error::ContractError;
 use crate::execute::{
-    execute_set_accepted_token, execute_set_admin, execute_set_signer, execute_stake,
+    execute_claim_rewards, execute_migrate_legacy_stakes, execute_receive,
+    execute_resolve_legacy_total, execute_set_accepted_token, execute_set_admin,
+    execute_set_duration_multiplier, execute_set_lock_duration, execute_set_max_stake_duration,
+    execute_set_reward_pool, execute_set_signature_scheme, execute_set_signer, execute_stake,
     execute_transfer_ownership, execute_unstake,
 };
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query::{
-    query_accepted_token, query_admin, query_owner, query_signer, query_staked_data,
-    query_total_staked, query_used_nonce,
+    query_accepted_token, query_admin, query_duration_multiplier, query_legacy_totals,
+    query_lock_durations, query_max_stake_duration, query_owner, query_pending_rewards,
+    query_reward_pool, query_signature_scheme, query_signer, query_staked_data, query_token_stakes,
+    query_token_total_staked, query_total_staked, query_used_nonce,
 };
-use crate::state::{set_admin, OWNER};
+use crate::state::{migrate_legacy_stakes, set_admin, OWNER};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:monsterra-staking";
@@ -47,13 +53,34 @@ pub fn instantiate(
 /// - only contract admin can migrate, so admin has to be set at contract initiation time
 /// Handling contract execution
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    match msg {
-        // Find matched incoming message variant and execute them with your custom logic.
-        //
-        // With `Response` type, it is possible to dispatch message to invoke external logic.
-        // See: https://github.com/CosmWasm/cosmwasm/blob/main/SEMANTICS.md#dispatching-messages
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {});
+    }
+
+    let stored_version =
+        Version::parse(&stored.version).map_err(|_| ContractError::InvalidContractVersion {})?;
+    let current_version =
+        Version::parse(CONTRACT_VERSION).map_err(|_| ContractError::InvalidContractVersion {})?;
+    if stored_version > current_version {
+        return Err(ContractError::CannotDowngrade {});
     }
+
+    // 0.2.0 split positions and totals into their own namespaces. The first batch
+    // is moved here, the rest through MigrateLegacyStakes.
+    let mut remaining = false;
+    if stored_version < Version::new(0, 2, 0) {
+        remaining = migrate_legacy_stakes(deps.storage, None)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("legacy_stakes_remaining", remaining.to_string())
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
 
 /// Handling contract execution
@@ -72,31 +99,92 @@ pub fn execute(
             execute_set_admin(deps.storage, info, user, status)
         }
         ExecuteMsg::SetSigner { public_key } => execute_set_signer(deps.storage, info, public_key),
+        ExecuteMsg::SetSignatureScheme { scheme } => {
+            execute_set_signature_scheme(deps.storage, info, scheme)
+        }
         ExecuteMsg::SetAcceptedToken { token, status } => {
             execute_set_accepted_token(deps.storage, info, token, status)
         }
+        ExecuteMsg::SetMaxStakeDuration { duration } => {
+            execute_set_max_stake_duration(deps.storage, info, duration)
+        }
+        ExecuteMsg::SetLockDuration { duration, status } => {
+            execute_set_lock_duration(deps.storage, info, duration, status)
+        }
         ExecuteMsg::Stake {
             token,
             amount,
             duration,
         } => execute_stake(deps.storage, env, info, &token, amount, duration),
         ExecuteMsg::Unstake { msg, signature } => execute_unstake(deps, env, info, msg, signature),
+        ExecuteMsg::SetDurationMultiplier {
+            duration,
+            multiplier,
+        } => execute_set_duration_multiplier(deps.storage, info, duration, multiplier),
+        ExecuteMsg::SetRewardPool {
+            staked_token,
+            reward_token,
+            emission_per_second,
+        } => execute_set_reward_pool(
+            deps.storage,
+            env,
+            info,
+            staked_token,
+            reward_token,
+            emission_per_second,
+        ),
+        ExecuteMsg::ClaimRewards {} => execute_claim_rewards(deps, env, info),
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+        ExecuteMsg::MigrateLegacyStakes { limit } => {
+            execute_migrate_legacy_stakes(deps.storage, info, limit)
+        }
+        ExecuteMsg::ResolveLegacyTotal { user, positions } => {
+            execute_resolve_legacy_total(deps.storage, env, info, user, positions)
+        }
     }
 }
 
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetOwner {} => to_binary(&query_owner(deps.storage)),
         QueryMsg::IsAdmin { user } => to_binary(&query_admin(deps.storage, user)),
         QueryMsg::GetSigner {} => to_binary(&query_signer(deps.storage)),
+        QueryMsg::GetSignatureScheme {} => to_binary(&query_signature_scheme(deps.storage)),
         QueryMsg::IsAcceptedToken { token } => {
             to_binary(&query_accepted_token(deps.storage, token))
         }
+        QueryMsg::GetMaxStakeDuration {} => to_binary(&query_max_stake_duration(deps.storage)),
+        QueryMsg::GetLockDurations {} => to_binary(&query_lock_durations(deps.storage)),
         QueryMsg::GetTotalStaked { user } => to_binary(&query_total_staked(deps.storage, user)),
+        QueryMsg::GetTokenTotalStaked { token } => {
+            to_binary(&query_token_total_staked(deps.storage, token))
+        }
         QueryMsg::GetStakeData { user } => to_binary(&query_staked_data(deps.storage, user)),
+        QueryMsg::GetTokenStakes {
+            token,
+            start_after,
+            limit,
+        } => to_binary(&query_token_stakes(
+            deps.storage,
+            token,
+            start_after,
+            limit,
+        )?),
         QueryMsg::IsUsedNonce { nonce } => to_binary(&query_used_nonce(deps.storage, nonce)),
+        QueryMsg::GetRewardPool { staked_token } => {
+            to_binary(&query_reward_pool(deps.storage, staked_token))
+        }
+        QueryMsg::GetDurationMultiplier { duration } => {
+            to_binary(&query_duration_multiplier(deps.storage, duration))
+        }
+        QueryMsg::PendingRewards { user } => {
+            to_binary(&query_pending_rewards(deps.storage, user, env.block.time))
+        }
+        QueryMsg::GetLegacyTotals { start_after, limit } => {
+            to_binary(&query_legacy_totals(deps.storage, start_after, limit)?)
+        }
     }
 }
 
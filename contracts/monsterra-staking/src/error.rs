@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use monsterra_signing::SigningError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +7,24 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Signing(#[from] SigningError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("InvalidContractName")]
+    InvalidContractName {},
+
+    #[error("InvalidContractVersion")]
+    InvalidContractVersion {},
+
+    #[error("CannotDowngrade")]
+    CannotDowngrade {},
+
+    #[error("LegacyTotalNotFound")]
+    LegacyTotalNotFound {},
+
     #[error("NotAcceptedToken")]
     NotAcceptedToken {},
 
@@ -18,6 +34,27 @@ pub enum ContractError {
     #[error("TimeExpired")]
     TimeExpired {},
 
+    #[error("InvalidDuration")]
+    InvalidDuration {},
+
+    #[error("StakeLocked")]
+    StakeLocked {},
+
+    #[error("StakeNotFound")]
+    StakeNotFound {},
+
+    #[error("InsufficientStake")]
+    InsufficientStake {},
+
+    #[error("InvalidMultiplier")]
+    InvalidMultiplier {},
+
+    #[error("RewardPoolNotFound")]
+    RewardPoolNotFound {},
+
+    #[error("InvalidRewardToken")]
+    InvalidRewardToken {},
+
     #[error("InvalidSignature")]
     InvalidSignature {},
 
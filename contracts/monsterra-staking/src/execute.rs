@@ -1,16 +1,21 @@
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    Storage, Uint128, WasmMsg,
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, Storage, Uint128, WasmMsg,
 };
-use cw20::Cw20ExecuteMsg;
-use sha2::{Digest, Sha256};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use monsterra_signing::{get_signature_scheme, set_signature_scheme, verify_signature};
 
 use crate::{
-    msg::{UnstakeMsg, UnstakePayload},
+    msg::{ReceiveMsg, UnstakeMsg, UnstakePayload},
     state::{
-        get_signer, get_total_staked, is_accepted_token, is_used_nonce, set_accepted_token,
-        set_admin, set_new_owner, set_signer, set_staked_data, set_total_staked, set_used_nonce,
-        StakeData,
+        get_duration_multiplier, get_reward_pool, get_signer, get_stake, get_staked_data,
+        get_token_total_staked, get_total_staked, is_accepted_token, is_admin, is_allowed_duration,
+        is_used_nonce, join_reward_pool, migrate_legacy_stakes, mul_floor, next_stake_id,
+        resolve_legacy_total, save_reward_pool, set_accepted_token, set_admin,
+        set_duration_multiplier, set_lock_duration, set_max_stake_duration, set_new_owner,
+        set_reward_pool, set_signer, set_staked_data, set_token_total_staked, set_total_staked,
+        set_used_nonce, settle_stake_rewards, take_unclaimed_rewards, update_stake,
+        SignatureScheme, StakeData, DURATION_UNIT_SECONDS, SIGNATURE_SCHEME, SIGNER,
     },
     ContractError,
 };
@@ -49,6 +54,91 @@ pub fn execute_set_signer(
     set_signer(storage, &info, public_key)
 }
 
+pub fn execute_set_signature_scheme(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    scheme: SignatureScheme,
+) -> Result<Response, ContractError> {
+    let admin = is_admin(storage, info.sender);
+    let signer = SIGNER.may_load(storage)?;
+    Ok(set_signature_scheme(
+        storage,
+        &SIGNATURE_SCHEME,
+        admin,
+        scheme,
+        signer.as_deref(),
+    )?)
+}
+
+pub fn execute_set_max_stake_duration(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    duration: u8,
+) -> Result<Response, ContractError> {
+    set_max_stake_duration(storage, &info, duration)
+}
+
+pub fn execute_set_lock_duration(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    duration: u8,
+    status: bool,
+) -> Result<Response, ContractError> {
+    set_lock_duration(storage, &info, duration, status)
+}
+
+pub fn execute_set_duration_multiplier(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    duration: u8,
+    multiplier: Decimal,
+) -> Result<Response, ContractError> {
+    set_duration_multiplier(storage, &info, duration, multiplier)
+}
+
+pub fn execute_set_reward_pool(
+    storage: &mut dyn Storage,
+    env: Env,
+    info: MessageInfo,
+    staked_token: Addr,
+    reward_token: Addr,
+    emission_per_second: Uint128,
+) -> Result<Response, ContractError> {
+    set_reward_pool(
+        storage,
+        &info,
+        staked_token,
+        reward_token,
+        emission_per_second,
+        env.block.time,
+    )
+}
+
+pub fn execute_migrate_legacy_stakes(
+    storage: &mut dyn Storage,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let remaining = migrate_legacy_stakes(storage, limit)?;
+    Ok(Response::new()
+        .add_attribute("method", "migrate_legacy_stakes")
+        .add_attribute("remaining", remaining.to_string()))
+}
+
+pub fn execute_resolve_legacy_total(
+    storage: &mut dyn Storage,
+    env: Env,
+    info: MessageInfo,
+    user: Addr,
+    positions: Vec<(Addr, Uint128)>,
+) -> Result<Response, ContractError> {
+    resolve_legacy_total(storage, &info, user, positions, env.block.time)
+}
+
 pub fn execute_stake(
     storage: &mut dyn Storage,
     env: Env,
@@ -61,6 +151,15 @@ pub fn execute_stake(
         return Err(ContractError::NotAcceptedToken {});
     }
 
+    if !is_allowed_duration(storage, duration) {
+        return Err(ContractError::InvalidDuration {});
+    }
+
+    let unlock_at = env
+        .block
+        .time
+        .plus_seconds(duration as u64 * DURATION_UNIT_SECONDS);
+
     let mut messages: Vec<CosmosMsg> = vec![];
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: token.to_string(),
@@ -72,16 +171,26 @@ pub fn execute_stake(
         funds: vec![],
     }));
 
-    set_staked_data(
-        storage,
-        &info.sender,
-        StakeData {
-            amount,
-            duration,
-            token: token.clone(),
-            time: env.block.time,
-        },
-    )?;
+    // positions earn on their weight, so the multiplier is fixed at stake time
+    let mut data = StakeData {
+        id: next_stake_id(storage)?,
+        amount,
+        duration,
+        token: token.clone(),
+        time: env.block.time,
+        unlock_at,
+        weight: mul_floor(amount, get_duration_multiplier(storage, duration))?,
+        reward_debt: Uint128::zero(),
+        in_reward_pool: false,
+    };
+    if let Some(mut pool) = get_reward_pool(storage, token.clone()) {
+        pool.update(env.block.time)?;
+        join_reward_pool(storage, &mut data, &mut pool)?;
+        save_reward_pool(storage, token, &pool)?;
+    }
+
+    let stake_id = data.id;
+    set_staked_data(storage, &info.sender, data)?;
 
     let mut total_staked = get_total_staked(storage, info.sender.clone());
 
@@ -91,14 +200,21 @@ pub fn execute_stake(
 
     set_total_staked(storage, &info.sender.clone(), total_staked)?;
 
+    let token_total_staked = get_token_total_staked(storage, token.clone())
+        .checked_add(amount)
+        .map_err(StdError::overflow)?;
+    set_token_total_staked(storage, token, token_total_staked)?;
+
     Ok(Response::new()
         .add_messages(messages)
         .add_attribute("method", "stake")
         .add_attributes(vec![
             ("sender", info.sender.to_string()),
+            ("stake_id", stake_id.to_string()),
             ("token", token.to_string()),
             ("amount", amount.to_string()),
             ("duration", duration.to_string()),
+            ("unlock_at", unlock_at.to_string()),
         ]))
 }
 
@@ -110,12 +226,18 @@ pub fn execute_unstake(
     signature: Binary,
 ) -> Result<Response, ContractError> {
     let UnstakeMsg {
-        ref token,
+        stake_id,
         amount,
         nonce,
         timestamp,
     } = msg;
 
+    let mut stake = match get_stake(deps.storage, info.sender.clone(), stake_id) {
+        Some(value) => value,
+        None => return Err(ContractError::StakeNotFound {}),
+    };
+    let token = stake.token.clone();
+
     if !is_accepted_token(deps.storage, token.clone()) {
         return Err(ContractError::NotAcceptedToken {});
     }
@@ -130,10 +252,19 @@ pub fn execute_unstake(
 
     set_used_nonce(deps.storage, nonce.clone(), true)?;
 
+    if stake.unlock_at > env.block.time {
+        return Err(ContractError::StakeLocked {});
+    }
+
+    if amount.is_zero() || amount > stake.amount {
+        return Err(ContractError::InsufficientStake {});
+    }
+
     if !verify_sig(
         deps.as_ref(),
         &UnstakePayload {
             sender: info.sender.clone(),
+            stake_id,
             token: token.clone(),
             amount,
             nonce,
@@ -144,12 +275,41 @@ pub fn execute_unstake(
         return Err(ContractError::InvalidSignature {});
     }
 
+    let remaining = stake.amount - amount;
+    if let Some(mut pool) = get_reward_pool(deps.storage, token.clone()) {
+        pool.update(env.block.time)?;
+        join_reward_pool(deps.storage, &mut stake, &mut pool)?;
+        settle_stake_rewards(deps.storage, &info.sender, &mut stake, &pool)?;
+        let weight = stake.weight.multiply_ratio(remaining, stake.amount);
+        pool.total_weight = pool
+            .total_weight
+            .checked_sub(stake.weight - weight)
+            .map_err(StdError::overflow)?;
+        stake.reward_debt = mul_floor(weight, pool.acc_reward_per_share)?;
+        stake.weight = weight;
+        save_reward_pool(deps.storage, &token, &pool)?;
+    } else {
+        stake.weight = stake.weight.multiply_ratio(remaining, stake.amount);
+    }
+    stake.amount = remaining;
+    update_stake(deps.storage, &info.sender, stake)?;
+
+    let total_staked = get_total_staked(deps.storage, info.sender.clone())
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    set_total_staked(deps.storage, &info.sender, total_staked)?;
+
+    let token_total_staked = get_token_total_staked(deps.storage, token.clone())
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    set_token_total_staked(deps.storage, &token, token_total_staked)?;
+
     let mut messages: Vec<CosmosMsg> = vec![];
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: msg.token.to_string(),
+        contract_addr: token.to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Transfer {
             recipient: info.sender.to_string(),
-            amount: msg.amount,
+            amount,
         })?,
         funds: vec![],
     }));
@@ -159,8 +319,109 @@ pub fn execute_unstake(
         .add_attribute("method", "unstake")
         .add_attributes(vec![
             ("sender", info.sender.to_string()),
-            ("token", msg.token.to_string()),
-            ("amount", msg.amount.to_string()),
+            ("stake_id", stake_id.to_string()),
+            ("token", token.to_string()),
+            ("amount", amount.to_string()),
+        ]))
+}
+
+pub fn execute_claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    for mut data in get_staked_data(deps.storage, info.sender.clone()) {
+        let mut pool = match get_reward_pool(deps.storage, data.token.clone()) {
+            Some(value) => value,
+            None => continue,
+        };
+        pool.update(env.block.time)?;
+        join_reward_pool(deps.storage, &mut data, &mut pool)?;
+        settle_stake_rewards(deps.storage, &info.sender, &mut data, &pool)?;
+        save_reward_pool(deps.storage, &data.token, &pool)?;
+        update_stake(deps.storage, &info.sender, data)?;
+    }
+
+    // positions staked before their pool existed join it above and only earn from
+    // now on, so a claim that pays nothing still succeeds to record the join
+    let rewards = take_unclaimed_rewards(deps.storage, &info.sender)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut response = Response::new()
+        .add_attribute("method", "claim_rewards")
+        .add_attribute("sender", info.sender.to_string());
+    for (reward_token, amount) in rewards {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: reward_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }));
+        response = response
+            .add_attribute("reward_token", reward_token.to_string())
+            .add_attribute("amount", amount.to_string());
+    }
+
+    Ok(response.add_messages(messages))
+}
+
+// CW20 `Send` hook, info.sender is the token contract
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match from_binary(&wrapper.msg)? {
+        ReceiveMsg::FundRewards { staked_token } => execute_fund_rewards(
+            deps.storage,
+            env,
+            info,
+            sender,
+            staked_token,
+            wrapper.amount,
+        ),
+    }
+}
+
+pub fn execute_fund_rewards(
+    storage: &mut dyn Storage,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    staked_token: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pool = match get_reward_pool(storage, staked_token.clone()) {
+        Some(value) => value,
+        None => return Err(ContractError::RewardPoolNotFound {}),
+    };
+    if pool.reward_token != info.sender {
+        return Err(ContractError::InvalidRewardToken {});
+    }
+
+    // emit what was owed so far before the new funds become available
+    pool.update(env.block.time)?;
+    pool.balance = pool
+        .balance
+        .checked_add(amount)
+        .map_err(StdError::overflow)?;
+    save_reward_pool(storage, &staked_token, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "fund_rewards")
+        .add_attributes(vec![
+            ("sender", sender.to_string()),
+            ("staked_token", staked_token.to_string()),
+            ("reward_token", info.sender.to_string()),
+            ("amount", amount.to_string()),
         ]))
 }
 
@@ -172,13 +433,90 @@ fn verify_sig(deps: Deps, unstake_payload: &UnstakePayload, signature: Binary) -
         Err(_) => return false,
     };
     let signer = get_signer(deps.storage);
+    let scheme = get_signature_scheme(deps.storage, &SIGNATURE_SCHEME);
 
-    let hash = Sha256::digest(msg);
-    let result = deps
-        .api
-        .secp256k1_verify(hash.as_ref(), &signature, &signer.0);
-    match result {
-        Ok(value) => value,
-        Err(_) => false,
+    verify_signature(deps.api, &scheme, &msg, &signature, &signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{ACCEPTED_TOKENS, ADMIN, LOCK_DURATIONS};
+    use cosmwasm_std::attr;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    #[test]
+    fn positions_staked_before_the_pool_join_on_claim() {
+        let mut deps = mock_dependencies();
+        let token = Addr::unchecked("token");
+        let reward_token = Addr::unchecked("reward");
+        let admin = Addr::unchecked("admin");
+        ADMIN
+            .save(deps.as_mut().storage, admin.clone(), &true)
+            .unwrap();
+        ACCEPTED_TOKENS
+            .save(deps.as_mut().storage, token.clone(), &true)
+            .unwrap();
+        LOCK_DURATIONS
+            .save(deps.as_mut().storage, 0, &true)
+            .unwrap();
+
+        let mut env = mock_env();
+        execute_stake(
+            deps.as_mut().storage,
+            env.clone(),
+            mock_info("user", &[]),
+            &token,
+            Uint128::new(100),
+            0,
+        )
+        .unwrap();
+        execute_set_reward_pool(
+            deps.as_mut().storage,
+            env.clone(),
+            mock_info("admin", &[]),
+            token.clone(),
+            reward_token.clone(),
+            Uint128::new(10),
+        )
+        .unwrap();
+        execute_fund_rewards(
+            deps.as_mut().storage,
+            env.clone(),
+            mock_info("reward", &[]),
+            admin,
+            token.clone(),
+            Uint128::new(1000),
+        )
+        .unwrap();
+
+        // the first claim only joins the pool, nothing was earned before it
+        env.block.time = env.block.time.plus_seconds(10);
+        let res =
+            execute_claim_rewards(deps.as_mut(), env.clone(), mock_info("user", &[])).unwrap();
+        assert!(res.messages.is_empty());
+        let pool = get_reward_pool(deps.as_ref().storage, token.clone()).unwrap();
+        assert_eq!(pool.total_weight, Uint128::new(100));
+        assert!(
+            get_stake(deps.as_ref().storage, Addr::unchecked("user"), 1)
+                .unwrap()
+                .in_reward_pool
+        );
+
+        env.block.time = env.block.time.plus_seconds(10);
+        let res = execute_claim_rewards(deps.as_mut(), env, mock_info("user", &[])).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: reward_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "user".to_string(),
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert!(res.attributes.contains(&attr("amount", "100")));
     }
 }
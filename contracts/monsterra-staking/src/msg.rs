@@ -1,7 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
 
-use crate::state::StakeData;
+use crate::state::{RewardPool, SignatureScheme, StakeData};
 
 /// Message type for `instantiate` entry_point
 #[cw_serde]
@@ -24,6 +25,17 @@ pub enum ExecuteMsg {
     SetSigner {
         public_key: Binary,
     },
+    SetSignatureScheme {
+        scheme: SignatureScheme,
+    },
+    // durations are in days
+    SetMaxStakeDuration {
+        duration: u8,
+    },
+    SetLockDuration {
+        duration: u8,
+        status: bool,
+    },
     Stake {
         token: Addr,
         amount: Uint128,
@@ -33,11 +45,39 @@ pub enum ExecuteMsg {
         msg: UnstakeMsg,
         signature: Binary,
     },
+    // applies to positions staked after the change
+    SetDurationMultiplier {
+        duration: u8,
+        multiplier: Decimal,
+    },
+    SetRewardPool {
+        staked_token: Addr,
+        reward_token: Addr,
+        emission_per_second: Uint128,
+    },
+    ClaimRewards {},
+    Receive(Cw20ReceiveMsg),
+    // admin only, moves up to `limit` users of the pre 0.2.0 layout
+    MigrateLegacyStakes {
+        limit: Option<u32>,
+    },
+    // admin only, assigns a parked legacy total to (token, amount) positions
+    ResolveLegacyTotal {
+        user: Addr,
+        positions: Vec<(Addr, Uint128)>,
+    },
+}
+
+/// Message type for the CW20 `Receive` hook
+#[cw_serde]
+pub enum ReceiveMsg {
+    // admin only, the sent tokens must be the pool's reward token
+    FundRewards { staked_token: Addr },
 }
 
 #[cw_serde]
 pub struct UnstakeMsg {
-    pub token: Addr,
+    pub stake_id: u64,
     pub amount: Uint128,
     pub nonce: String,
     pub timestamp: Timestamp,
@@ -46,6 +86,7 @@ pub struct UnstakeMsg {
 #[cw_serde]
 pub struct UnstakePayload {
     pub sender: Addr,
+    pub stake_id: u64,
     pub token: Addr,
     pub amount: Uint128,
     pub nonce: String,
@@ -54,7 +95,7 @@ pub struct UnstakePayload {
 
 /// Message type for `migrate` entry_point
 #[cw_serde]
-pub enum MigrateMsg {}
+pub struct MigrateMsg {}
 
 /// Message type for `query` entry_point
 #[cw_serde]
@@ -75,18 +116,51 @@ pub enum QueryMsg {
     IsAcceptedToken { token: Addr },
     #[returns(Binary)]
     GetSigner {},
+    #[returns(SignatureScheme)]
+    GetSignatureScheme {},
+    #[returns(u8)]
+    GetMaxStakeDuration {},
+    #[returns(Vec<u8>)]
+    GetLockDurations {},
     #[returns(Uint128)]
     GetTotalStaked { user: Addr },
+    #[returns(Uint128)]
+    GetTokenTotalStaked { token: Addr },
     #[returns(Vec<StakeData>)]
     GetStakeData { user: Addr },
+    // positions of every user in `token`, keyed by (user, stake id)
+    #[returns(Vec<((Addr, u64), StakeData)>)]
+    GetTokenStakes {
+        token: Addr,
+        start_after: Option<(Addr, u64)>,
+        limit: Option<u32>,
+    },
     #[returns(bool)]
     IsUsedNonce { nonce: String },
+    #[returns(Option<RewardPool>)]
+    GetRewardPool { staked_token: Addr },
+    #[returns(Decimal)]
+    GetDurationMultiplier { duration: u8 },
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { user: Addr },
+    // legacy totals waiting for ResolveLegacyTotal
+    #[returns(Vec<(Addr, Uint128)>)]
+    GetLegacyTotals {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
 }
 
 // We define a custom struct for each query response
 // #[cw_serde]
 // pub struct YourQueryResponse {}
 
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    // (reward_token, amount) pairs
+    pub rewards: Vec<(Addr, Uint128)>,
+}
+
 #[cw_serde]
 pub struct StakeDataResponse {
     stake_data: Vec<StakeData>,
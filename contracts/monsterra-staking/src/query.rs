@@ -1,8 +1,13 @@
-use cosmwasm_std::{Addr, Binary, Storage, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, StdResult, Storage, Timestamp, Uint128};
 
+use monsterra_signing::get_signature_scheme;
+
+use crate::msg::PendingRewardsResponse;
 use crate::state::{
-    get_owner, get_signer, get_staked_data, get_total_staked, is_accepted_token, is_admin,
-    is_used_nonce, StakeData,
+    get_duration_multiplier, get_legacy_totals, get_lock_durations, get_max_stake_duration,
+    get_owner, get_pending_rewards, get_reward_pool, get_signer, get_staked_data, get_token_stakes,
+    get_token_total_staked, get_total_staked, is_accepted_token, is_admin, is_used_nonce,
+    RewardPool, SignatureScheme, StakeData, SIGNATURE_SCHEME,
 };
 
 pub fn query_owner(storage: &dyn Storage) -> Addr {
@@ -17,18 +22,69 @@ pub fn query_signer(storage: &dyn Storage) -> Binary {
     get_signer(storage)
 }
 
+pub fn query_signature_scheme(storage: &dyn Storage) -> SignatureScheme {
+    get_signature_scheme(storage, &SIGNATURE_SCHEME)
+}
+
 pub fn query_accepted_token(storage: &dyn Storage, token: Addr) -> bool {
     is_accepted_token(storage, token)
 }
 
+pub fn query_max_stake_duration(storage: &dyn Storage) -> u8 {
+    get_max_stake_duration(storage)
+}
+
+pub fn query_lock_durations(storage: &dyn Storage) -> Vec<u8> {
+    get_lock_durations(storage)
+}
+
 pub fn query_total_staked(storage: &dyn Storage, user: Addr) -> Uint128 {
     get_total_staked(storage, user)
 }
 
+pub fn query_token_total_staked(storage: &dyn Storage, token: Addr) -> Uint128 {
+    get_token_total_staked(storage, token)
+}
+
 pub fn query_staked_data(storage: &dyn Storage, user: Addr) -> Vec<StakeData> {
     get_staked_data(storage, user)
 }
 
+pub fn query_token_stakes(
+    storage: &dyn Storage,
+    token: Addr,
+    start_after: Option<(Addr, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<((Addr, u64), StakeData)>> {
+    get_token_stakes(storage, token, start_after, limit)
+}
+
+pub fn query_reward_pool(storage: &dyn Storage, staked_token: Addr) -> Option<RewardPool> {
+    get_reward_pool(storage, staked_token)
+}
+
+pub fn query_duration_multiplier(storage: &dyn Storage, duration: u8) -> Decimal {
+    get_duration_multiplier(storage, duration)
+}
+
+pub fn query_pending_rewards(
+    storage: &dyn Storage,
+    user: Addr,
+    now: Timestamp,
+) -> PendingRewardsResponse {
+    PendingRewardsResponse {
+        rewards: get_pending_rewards(storage, user, now),
+    }
+}
+
+pub fn query_legacy_totals(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    get_legacy_totals(storage, start_after, limit)
+}
+
 pub fn query_used_nonce(storage: &dyn Storage, nonce: String) -> bool {
     is_used_nonce(storage, nonce)
 }
@@ -2,28 +2,134 @@
 // see: https://crates.io/crates/cw-storage-plus
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Addr, Binary, MessageInfo, Response, Storage, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    Timestamp, Uint128, Uint256,
+};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+pub use monsterra_signing::SignatureScheme;
+use monsterra_signing::{get_signature_scheme, validate_signer};
 
 use crate::ContractError;
 
 #[cw_serde]
 pub struct StakeData {
+    // zero for stakes made before positions had ids
+    #[serde(default)]
+    pub id: u64,
+    // remaining balance of the position
     pub amount: Uint128,
     pub duration: u8,
     pub token: Addr,
     pub time: Timestamp,
+    // zero for stakes made before lock periods were enforced on-chain
+    #[serde(default)]
+    pub unlock_at: Timestamp,
+    // amount scaled by the duration multiplier, what the position earns rewards on
+    #[serde(default)]
+    pub weight: Uint128,
+    // weight * acc_reward_per_share as of the last settlement
+    #[serde(default)]
+    pub reward_debt: Uint128,
+    // whether the weight is counted in the token's reward pool, positions staked
+    // before the pool existed join on their first touch, see `join_reward_pool`
+    #[serde(default)]
+    pub in_reward_pool: bool,
+}
+
+// Rewards paid in `reward_token` to the stakers of one token
+#[cw_serde]
+pub struct RewardPool {
+    pub reward_token: Addr,
+    pub emission_per_second: Uint128,
+    // rewards emitted per unit of position weight since the pool was created
+    pub acc_reward_per_share: Decimal,
+    pub total_weight: Uint128,
+    // funded rewards that have not been emitted yet
+    pub balance: Uint128,
+    pub last_update: Timestamp,
+}
+
+impl RewardPool {
+    // Emits rewards for the time since last_update, bounded by the funded balance.
+    // The pool is left untouched when the accounting fails.
+    pub fn update(&mut self, now: Timestamp) -> StdResult<()> {
+        if now <= self.last_update {
+            return Ok(());
+        }
+        if !self.total_weight.is_zero() {
+            let elapsed = Uint128::from(now.seconds() - self.last_update.seconds());
+            // the cap keeps the per share increase within Decimal's range for tiny weights
+            let max_reward = self
+                .total_weight
+                .saturating_mul(Uint128::new(u128::MAX / 1_000_000_000_000_000_000));
+            let reward = self
+                .emission_per_second
+                .saturating_mul(elapsed)
+                .min(self.balance)
+                .min(max_reward);
+            let per_share = Decimal::from_ratio(reward, self.total_weight);
+            let acc_reward_per_share = self
+                .acc_reward_per_share
+                .checked_add(per_share)
+                .map_err(StdError::overflow)?;
+            // rounding dust stays in the balance
+            let balance = self
+                .balance
+                .checked_sub(mul_floor(self.total_weight, per_share)?)
+                .map_err(StdError::overflow)?;
+            self.acc_reward_per_share = acc_reward_per_share;
+            self.balance = balance;
+        }
+        self.last_update = now;
+        Ok(())
+    }
+
+    pub fn pending(&self, data: &StakeData) -> StdResult<Uint128> {
+        if !data.in_reward_pool {
+            return Ok(Uint128::zero());
+        }
+        Ok(mul_floor(data.weight, self.acc_reward_per_share)?.saturating_sub(data.reward_debt))
+    }
+}
+
+// `value * ratio` rounded down, an error rather than a panic when it does not fit
+pub fn mul_floor(value: Uint128, ratio: Decimal) -> StdResult<Uint128> {
+    let product = Uint256::from(value)
+        .checked_mul(Uint256::from(ratio.atomics()))
+        .map_err(StdError::overflow)?
+        / Uint256::from(Decimal::one().atomics());
+    Ok(Uint128::try_from(product)?)
 }
 
 pub const OWNER: Item<Addr> = Item::new("owner");
 pub const ADMIN: Map<Addr, bool> = Map::new("admin");
 pub const SIGNER: Item<Binary> = Item::new("signer");
+pub const SIGNATURE_SCHEME: Item<SignatureScheme> = Item::new("signature_scheme");
 
 pub const ACCEPTED_TOKENS: Map<Addr, bool> = Map::new("accepted_token");
 pub const MAX_STAKE_DURATION: Item<u8> = Item::new("max_stake_duration");
-pub const TOTAL_STAKED: Map<Addr, Uint128> = Map::new("total_staked");
-pub const STAKED_DATA: Map<Addr, Vec<StakeData>> = Map::new("total_staked");
+pub const LOCK_DURATIONS: Map<u8, bool> = Map::new("lock_durations");
+pub const TOTAL_STAKED: Map<Addr, Uint128> = Map::new("user_total_staked");
+pub const STAKE_POSITIONS_KEY: &str = "stake_positions";
+// Storage layout before 0.2.0, read only by `migrate_legacy_stakes`
+pub const LEGACY_TOTAL_STAKED: Map<Addr, Uint128> = Map::new("total_staked");
+pub const LEGACY_STAKED_DATA: Map<Addr, Vec<StakeData>> = Map::new("total_staked");
+// Bare legacy totals waiting for an admin to reconcile them
+pub const LEGACY_TOTALS: Map<Addr, Uint128> = Map::new("legacy_totals");
+pub const TOKEN_TOTAL_STAKED: Map<Addr, Uint128> = Map::new("token_total_staked");
+pub const NEXT_STAKE_ID: Item<u64> = Item::new("next_stake_id");
 pub const USED_NONCES: Map<String, bool> = Map::new("used_nonces");
+pub const REWARD_POOLS: Map<Addr, RewardPool> = Map::new("reward_pools");
+pub const DURATION_MULTIPLIERS: Map<u8, Decimal> = Map::new("duration_multipliers");
+// (user, reward_token) => settled rewards waiting to be claimed
+pub const UNCLAIMED_REWARDS: Map<(Addr, Addr), Uint128> = Map::new("unclaimed_rewards");
+
+// Stake durations are counted in days
+pub const DURATION_UNIT_SECONDS: u64 = 86400;
+
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
 
 pub fn set_new_owner(
     storage: &mut dyn Storage,
@@ -81,6 +187,10 @@ pub fn set_signer(
     if !is_admin(storage, info.sender.clone()) {
         return Err(ContractError::Unauthorized {});
     }
+    validate_signer(
+        &get_signature_scheme(storage, &SIGNATURE_SCHEME),
+        &public_key,
+    )?;
 
     let result = SIGNER.save(storage, &public_key);
     match result {
@@ -130,6 +240,70 @@ pub fn is_accepted_token(storage: &dyn Storage, token: Addr) -> bool {
     }
 }
 
+pub fn set_max_stake_duration(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    duration: u8,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let result = MAX_STAKE_DURATION.save(storage, &duration);
+    match result {
+        Ok(_) => Ok(Response::new()
+            .add_attribute("method", "set_max_stake_duration")
+            .add_attribute("duration", duration.to_string())),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
+pub fn get_max_stake_duration(storage: &dyn Storage) -> u8 {
+    MAX_STAKE_DURATION.load(storage).unwrap_or(0)
+}
+
+pub fn set_lock_duration(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    duration: u8,
+    status: bool,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if status && duration > get_max_stake_duration(storage) {
+        return Err(ContractError::InvalidDuration {});
+    }
+
+    let result = LOCK_DURATIONS.save(storage, duration, &status);
+    match result {
+        Ok(_) => Ok(Response::new()
+            .add_attribute("method", "set_lock_duration")
+            .add_attribute("duration", duration.to_string())
+            .add_attribute("status", status.to_string())),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
+// A duration can be staked for when it is allowed and within MAX_STAKE_DURATION
+pub fn is_allowed_duration(storage: &dyn Storage, duration: u8) -> bool {
+    let result = LOCK_DURATIONS.load(storage, duration);
+    match result {
+        Ok(value) => value && duration <= get_max_stake_duration(storage),
+        Err(_) => false,
+    }
+}
+
+pub fn get_lock_durations(storage: &dyn Storage) -> Vec<u8> {
+    LOCK_DURATIONS
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .filter(|(duration, _)| is_allowed_duration(storage, *duration))
+        .map(|(duration, _)| duration)
+        .collect()
+}
+
 pub fn set_used_nonce(
     storage: &mut dyn Storage,
     nonce: String,
@@ -176,23 +350,40 @@ pub fn get_total_staked(storage: &dyn Storage, user: Addr) -> Uint128 {
     }
 }
 
+pub fn set_token_total_staked(
+    storage: &mut dyn Storage,
+    token: &Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let result = TOKEN_TOTAL_STAKED.save(storage, token.clone(), &amount);
+    match result {
+        Ok(_) => Ok(Response::new()
+            .add_attribute("method", "set_token_total_staked")
+            .add_attribute("token", token.to_string())
+            .add_attribute("amount", amount.to_string())),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
+pub fn get_token_total_staked(storage: &dyn Storage, token: Addr) -> Uint128 {
+    let result = TOKEN_TOTAL_STAKED.load(storage, token);
+    match result {
+        Ok(value) => value,
+        Err(_) => Uint128::zero(),
+    }
+}
+
 pub fn set_staked_data(
     storage: &mut dyn Storage,
     user: &Addr,
     data: StakeData,
 ) -> Result<Response, ContractError> {
-    let mut staked_data = match STAKED_DATA.load(storage, user.clone()) {
-        Ok(vec) => vec,
-        Err(_) => Vec::new(),
-    };
-
-    staked_data.push(data.clone());
-
-    let result = STAKED_DATA.save(storage, user.clone(), &staked_data);
+    let result = stake_positions().save(storage, (user.clone(), data.id), &data);
     match result {
         Ok(_) => Ok(Response::new()
             .add_attribute("method", "set_staked_data")
             .add_attribute("user", user.to_string())
+            .add_attribute("stake_id", data.id.to_string())
             .add_attribute("amount", data.amount.to_string())
             .add_attribute("duration", data.duration.to_string())
             .add_attribute("token", data.token.to_string())
@@ -201,10 +392,415 @@ pub fn set_staked_data(
     }
 }
 
+// Ids start at 1, 0 is left to positions staked before ids existed
+pub fn next_stake_id(storage: &mut dyn Storage) -> Result<u64, ContractError> {
+    let id = NEXT_STAKE_ID.may_load(storage)?.unwrap_or(1);
+    NEXT_STAKE_ID.save(storage, &(id + 1))?;
+    Ok(id)
+}
+
+pub fn get_stake(storage: &dyn Storage, user: Addr, id: u64) -> Option<StakeData> {
+    stake_positions()
+        .may_load(storage, (user, id))
+        .unwrap_or(None)
+}
+
+// Replaces the user's position with the same id, dropping it once it is empty
+pub fn update_stake(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    data: StakeData,
+) -> Result<(), ContractError> {
+    let key = (user.clone(), data.id);
+    if !stake_positions().has(storage, key.clone()) {
+        return Err(ContractError::StakeNotFound {});
+    }
+
+    let result = if data.amount.is_zero() {
+        stake_positions().remove(storage, key)
+    } else {
+        stake_positions().save(storage, key, &data)
+    };
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
 pub fn get_staked_data(storage: &dyn Storage, user: Addr) -> Vec<StakeData> {
-    let result = STAKED_DATA.load(storage, user);
+    stake_positions()
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .map(|(_, data)| data)
+        .collect()
+}
+
+pub fn get_token_stakes(
+    storage: &dyn Storage,
+    token: Addr,
+    start_after: Option<(Addr, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<((Addr, u64), StakeData)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    stake_positions()
+        .idx
+        .token
+        .prefix(token)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+// Before 0.2.0 the user totals and positions shared the "total_staked" namespace,
+// so each user's entry holds whichever of the two was written last. Positions are
+// moved into the indexed map. A bare total is not a reliable balance, it was never
+// decreased on unstake and sums every token, so it is parked in LEGACY_TOTALS until
+// an admin reconciles it, see `resolve_legacy_total`.
+//
+// At most `limit` users are moved per call. Moved entries are removed, so the next
+// call picks up where this one stopped. Returns whether any legacy entries remain.
+pub fn migrate_legacy_stakes(
+    storage: &mut dyn Storage,
+    limit: Option<u32>,
+) -> Result<bool, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let users = LEGACY_STAKED_DATA
+        .keys(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for user in users {
+        match LEGACY_STAKED_DATA.load(storage, user.clone()) {
+            Ok(positions) => {
+                for mut data in positions {
+                    if data.amount.is_zero() {
+                        continue;
+                    }
+                    if data.id == 0 {
+                        data.id = next_stake_id(storage)?;
+                    }
+                    stake_positions().save(storage, (user.clone(), data.id), &data)?;
+                    add_staked_totals(storage, &user, &data)?;
+                }
+            }
+            Err(_) => {
+                let total = LEGACY_TOTAL_STAKED.load(storage, user.clone())?;
+                if !total.is_zero() {
+                    LEGACY_TOTALS.save(storage, user.clone(), &total)?;
+                }
+            }
+        }
+        LEGACY_STAKED_DATA.remove(storage, user);
+    }
+
+    Ok(LEGACY_STAKED_DATA
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some())
+}
+
+// Turns a parked legacy total into unlocked positions once the admin has checked
+// the user's actual per token balances. The positions may not add up to more than
+// the parked total, whatever is not assigned is dropped with the entry.
+pub fn resolve_legacy_total(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    user: Addr,
+    positions: Vec<(Addr, Uint128)>,
+    now: Timestamp,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let total = match LEGACY_TOTALS.may_load(storage, user.clone())? {
+        Some(value) => value,
+        None => return Err(ContractError::LegacyTotalNotFound {}),
+    };
+    let mut assigned = Uint128::zero();
+    for (_, amount) in positions.iter() {
+        assigned = assigned.checked_add(*amount).map_err(StdError::overflow)?;
+    }
+    if assigned > total {
+        return Err(ContractError::InsufficientStake {});
+    }
+
+    for (token, amount) in positions {
+        if amount.is_zero() {
+            continue;
+        }
+        let data = StakeData {
+            id: next_stake_id(storage)?,
+            amount,
+            duration: 0,
+            token,
+            time: now,
+            unlock_at: Timestamp::default(),
+            weight: Uint128::zero(),
+            reward_debt: Uint128::zero(),
+            in_reward_pool: false,
+        };
+        stake_positions().save(storage, (user.clone(), data.id), &data)?;
+        add_staked_totals(storage, &user, &data)?;
+    }
+    LEGACY_TOTALS.remove(storage, user.clone());
+
+    Ok(Response::new()
+        .add_attribute("method", "resolve_legacy_total")
+        .add_attribute("user", user.to_string())
+        .add_attribute("legacy_total", total.to_string())
+        .add_attribute("assigned", assigned.to_string()))
+}
+
+pub fn get_legacy_totals(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    LEGACY_TOTALS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn add_staked_totals(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    data: &StakeData,
+) -> Result<(), ContractError> {
+    let total_staked = get_total_staked(storage, user.clone())
+        .checked_add(data.amount)
+        .map_err(StdError::overflow)?;
+    set_total_staked(storage, user, total_staked)?;
+
+    let token_total_staked = get_token_total_staked(storage, data.token.clone())
+        .checked_add(data.amount)
+        .map_err(StdError::overflow)?;
+    set_token_total_staked(storage, &data.token, token_total_staked)?;
+    Ok(())
+}
+
+pub fn set_duration_multiplier(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    duration: u8,
+    multiplier: Decimal,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if multiplier.is_zero() {
+        return Err(ContractError::InvalidMultiplier {});
+    }
+
+    let result = DURATION_MULTIPLIERS.save(storage, duration, &multiplier);
     match result {
-        Ok(value) => value,
-        Err(_) => Vec::new(),
+        Ok(_) => Ok(Response::new()
+            .add_attribute("method", "set_duration_multiplier")
+            .add_attribute("duration", duration.to_string())
+            .add_attribute("multiplier", multiplier.to_string())),
+        Err(_) => Err(ContractError::Internal {}),
     }
 }
+
+// Durations without an entry earn at 1x
+pub fn get_duration_multiplier(storage: &dyn Storage, duration: u8) -> Decimal {
+    DURATION_MULTIPLIERS
+        .load(storage, duration)
+        .unwrap_or_else(|_| Decimal::one())
+}
+
+// A new pool starts without weight, the token's existing positions join it on
+// their first touch. Later updates keep the accumulator and only change the
+// emission rate.
+pub fn set_reward_pool(
+    storage: &mut dyn Storage,
+    info: &MessageInfo,
+    staked_token: Addr,
+    reward_token: Addr,
+    emission_per_second: Uint128,
+    now: Timestamp,
+) -> Result<Response, ContractError> {
+    if !is_admin(storage, info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let pool = match get_reward_pool(storage, staked_token.clone()) {
+        Some(mut pool) => {
+            if pool.reward_token != reward_token {
+                return Err(ContractError::InvalidRewardToken {});
+            }
+            pool.update(now)?;
+            pool.emission_per_second = emission_per_second;
+            pool
+        }
+        None => RewardPool {
+            reward_token: reward_token.clone(),
+            emission_per_second,
+            acc_reward_per_share: Decimal::zero(),
+            total_weight: Uint128::zero(),
+            balance: Uint128::zero(),
+            last_update: now,
+        },
+    };
+
+    let result = REWARD_POOLS.save(storage, staked_token.clone(), &pool);
+    match result {
+        Ok(_) => Ok(Response::new()
+            .add_attribute("method", "set_reward_pool")
+            .add_attribute("staked_token", staked_token.to_string())
+            .add_attribute("reward_token", reward_token.to_string())
+            .add_attribute("emission_per_second", emission_per_second.to_string())),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
+pub fn get_reward_pool(storage: &dyn Storage, staked_token: Addr) -> Option<RewardPool> {
+    REWARD_POOLS.may_load(storage, staked_token).unwrap_or(None)
+}
+
+pub fn save_reward_pool(
+    storage: &mut dyn Storage,
+    staked_token: &Addr,
+    pool: &RewardPool,
+) -> Result<(), ContractError> {
+    let result = REWARD_POOLS.save(storage, staked_token.clone(), pool);
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ContractError::Internal {}),
+    }
+}
+
+// Counts a position staked before its token's pool existed into the pool's total
+// weight. It earns from the pool's current accumulator on, so `pool` must already
+// be updated to now.
+pub fn join_reward_pool(
+    storage: &dyn Storage,
+    data: &mut StakeData,
+    pool: &mut RewardPool,
+) -> Result<(), ContractError> {
+    if data.in_reward_pool {
+        return Ok(());
+    }
+    let weight = if data.weight.is_zero() {
+        mul_floor(data.amount, get_duration_multiplier(storage, data.duration))?
+    } else {
+        data.weight
+    };
+    let total_weight = pool
+        .total_weight
+        .checked_add(weight)
+        .map_err(StdError::overflow)?;
+    let reward_debt = mul_floor(weight, pool.acc_reward_per_share)?;
+
+    pool.total_weight = total_weight;
+    data.weight = weight;
+    data.reward_debt = reward_debt;
+    data.in_reward_pool = true;
+    Ok(())
+}
+
+// Moves what the position has earned into the user's unclaimed rewards and
+// re-bases its reward debt on the pool's accumulator
+pub fn settle_stake_rewards(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    data: &mut StakeData,
+    pool: &RewardPool,
+) -> Result<(), ContractError> {
+    let pending = pool.pending(data)?;
+    let reward_debt = mul_floor(data.weight, pool.acc_reward_per_share)?;
+    if !pending.is_zero() {
+        let key = (user.clone(), pool.reward_token.clone());
+        let unclaimed = UNCLAIMED_REWARDS
+            .may_load(storage, key.clone())?
+            .unwrap_or_default()
+            .checked_add(pending)
+            .map_err(StdError::overflow)?;
+        UNCLAIMED_REWARDS.save(storage, key, &unclaimed)?;
+    }
+    data.reward_debt = reward_debt;
+    Ok(())
+}
+
+// Removes and returns the user's settled rewards, one entry per reward token
+pub fn take_unclaimed_rewards(
+    storage: &mut dyn Storage,
+    user: &Addr,
+) -> Result<Vec<(Addr, Uint128)>, ContractError> {
+    let rewards = get_unclaimed_rewards(storage, user.clone());
+    for (reward_token, _) in rewards.iter() {
+        UNCLAIMED_REWARDS.remove(storage, (user.clone(), reward_token.clone()));
+    }
+    Ok(rewards)
+}
+
+pub fn get_unclaimed_rewards(storage: &dyn Storage, user: Addr) -> Vec<(Addr, Uint128)> {
+    UNCLAIMED_REWARDS
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok())
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect()
+}
+
+// Settled plus accrued rewards of the user as of `now`, one entry per reward token
+pub fn get_pending_rewards(
+    storage: &dyn Storage,
+    user: Addr,
+    now: Timestamp,
+) -> Vec<(Addr, Uint128)> {
+    let mut rewards = get_unclaimed_rewards(storage, user.clone());
+    for data in get_staked_data(storage, user) {
+        let mut pool = match get_reward_pool(storage, data.token.clone()) {
+            Some(value) => value,
+            None => continue,
+        };
+        if pool.update(now).is_err() {
+            continue;
+        }
+        let pending = pool.pending(&data).unwrap_or_default();
+        if pending.is_zero() {
+            continue;
+        }
+        match rewards
+            .iter_mut()
+            .find(|(token, _)| *token == pool.reward_token)
+        {
+            Some((_, amount)) => *amount = amount.saturating_add(pending),
+            None => rewards.push((pool.reward_token, pending)),
+        }
+    }
+    rewards
+}
+
+pub struct StakeIndexes<'a> {
+    pub token: MultiIndex<'a, Addr, StakeData, (Addr, u64)>,
+}
+
+impl<'a> IndexList<StakeData> for StakeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<StakeData>> + '_> {
+        let v: Vec<&dyn Index<StakeData>> = vec![&self.token];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn stake_token_idx(d: &StakeData) -> Addr {
+    d.token.clone()
+}
+
+// Positions keyed by (user, stake id)
+pub fn stake_positions<'a>() -> IndexedMap<'a, (Addr, u64), StakeData, StakeIndexes<'a>> {
+    let indexes = StakeIndexes {
+        token: MultiIndex::new(
+            stake_token_idx,
+            STAKE_POSITIONS_KEY,
+            "stake_positions__token",
+        ),
+    };
+    IndexedMap::new(STAKE_POSITIONS_KEY, indexes)
+}
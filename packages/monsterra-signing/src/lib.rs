@@ -0,0 +1,289 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Api, Response, StdError, Storage};
+use cw_storage_plus::Item;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SigningError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("InvalidSigner")]
+    InvalidSigner {},
+
+    #[error("IncompatibleSigners")]
+    IncompatibleSigners {},
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+}
+
+// How signer approvals are checked, see `verify_signature`
+#[cw_serde]
+pub enum SignatureScheme {
+    // secp256k1 over the SHA-256 hash of the message, signers are public keys
+    Secp256k1Sha256,
+    // secp256k1 over the keccak256 hash of the Ethereum-prefixed message,
+    // signers are public keys or 20 byte EVM addresses
+    Secp256k1Keccak,
+    // ed25519 over the raw message, signers are public keys
+    Ed25519,
+}
+
+impl SignatureScheme {
+    // Whether `signer` has the length of a key, or address, this scheme verifies against
+    pub fn accepts_signer(&self, signer: &[u8]) -> bool {
+        match self {
+            SignatureScheme::Secp256k1Sha256 => matches!(signer.len(), 33 | 65),
+            SignatureScheme::Secp256k1Keccak => matches!(signer.len(), 20 | 33 | 65),
+            SignatureScheme::Ed25519 => signer.len() == 32,
+        }
+    }
+}
+
+// Contracts store the scheme under their own key, it defaults to Secp256k1Sha256
+pub fn get_signature_scheme(
+    storage: &dyn Storage,
+    item: &Item<SignatureScheme>,
+) -> SignatureScheme {
+    item.may_load(storage)
+        .ok()
+        .flatten()
+        .unwrap_or(SignatureScheme::Secp256k1Sha256)
+}
+
+// Handles SetSignatureScheme for every contract. Only admins may switch the scheme,
+// and a scheme that any of the registered `signers` could not sign under is refused
+// so that switching never strands a signer.
+pub fn set_signature_scheme<'a>(
+    storage: &mut dyn Storage,
+    item: &Item<SignatureScheme>,
+    is_admin: bool,
+    scheme: SignatureScheme,
+    signers: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<Response, SigningError> {
+    if !is_admin {
+        return Err(SigningError::Unauthorized {});
+    }
+    if !signers
+        .into_iter()
+        .all(|signer| scheme.accepts_signer(signer))
+    {
+        return Err(SigningError::IncompatibleSigners {});
+    }
+    item.save(storage, &scheme)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_signature_scheme")
+        .add_attribute("scheme", format!("{:?}", scheme)))
+}
+
+pub fn validate_signer(scheme: &SignatureScheme, signer: &[u8]) -> Result<(), SigningError> {
+    if !scheme.accepts_signer(signer) {
+        return Err(SigningError::InvalidSigner {});
+    }
+    Ok(())
+}
+
+// Checks `signature` over `message` against `signer` under `scheme`.
+//
+// Secp256k1Keccak expects an Ethereum personal_sign signature, r (32) | s (32) | v (1)
+// with v in {0, 1, 27, 28}, over keccak256("\x19Ethereum Signed Message:\n" + len + message).
+// A 20 byte signer is treated as an EVM address and compared with the recovered key.
+pub fn verify_signature(
+    api: &dyn Api,
+    scheme: &SignatureScheme,
+    message: &[u8],
+    signature: &[u8],
+    signer: &[u8],
+) -> bool {
+    match scheme {
+        SignatureScheme::Secp256k1Sha256 => {
+            let hash = Sha256::digest(message);
+            api.secp256k1_verify(hash.as_ref(), signature, signer)
+                .unwrap_or(false)
+        }
+        SignatureScheme::Secp256k1Keccak => {
+            if signature.len() != 65 {
+                return false;
+            }
+            let recovery_param = match signature[64] {
+                27 | 28 => signature[64] - 27,
+                0 | 1 => signature[64],
+                _ => return false,
+            };
+            let mut prefixed =
+                format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+            prefixed.extend_from_slice(message);
+            let hash = Keccak256::digest(prefixed);
+            if signer.len() == 20 {
+                let public_key = match api.secp256k1_recover_pubkey(
+                    hash.as_ref(),
+                    &signature[..64],
+                    recovery_param,
+                ) {
+                    Ok(value) => value,
+                    Err(_) => return false,
+                };
+                // The address is the last 20 bytes of the keccak256 of the uncompressed key
+                Keccak256::digest(&public_key[1..])[12..] == *signer
+            } else {
+                api.secp256k1_verify(hash.as_ref(), &signature[..64], signer)
+                    .unwrap_or(false)
+            }
+        }
+        SignatureScheme::Ed25519 => api
+            .ed25519_verify(message, signature, signer)
+            .unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{MockApi, MockStorage};
+
+    fn decode_hex(value: &str) -> Vec<u8> {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn keccak_personal_sign_recovers_address() {
+        // web3.eth.accounts.sign("Some data", "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+        let api = MockApi::default();
+        let signature = decode_hex(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+             6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a029\
+             1c",
+        );
+        let address = decode_hex("2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+        let scheme = SignatureScheme::Secp256k1Keccak;
+
+        assert!(verify_signature(
+            &api,
+            &scheme,
+            b"Some data",
+            &signature,
+            &address
+        ));
+        assert!(!verify_signature(
+            &api,
+            &scheme,
+            b"Other data",
+            &signature,
+            &address
+        ));
+
+        let mut other_address = address.clone();
+        other_address[0] ^= 1;
+        assert!(!verify_signature(
+            &api,
+            &scheme,
+            b"Some data",
+            &signature,
+            &other_address
+        ));
+
+        // v as a 0/1 recovery id
+        let mut raw_v = signature.clone();
+        raw_v[64] -= 27;
+        assert!(verify_signature(
+            &api,
+            &scheme,
+            b"Some data",
+            &raw_v,
+            &address
+        ));
+    }
+
+    #[test]
+    fn ed25519_rfc8032_vector() {
+        // RFC 8032 section 7.1, TEST 2
+        let api = MockApi::default();
+        let public_key =
+            decode_hex("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c");
+        let signature = decode_hex(
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        );
+        let scheme = SignatureScheme::Ed25519;
+
+        assert!(verify_signature(
+            &api,
+            &scheme,
+            &[0x72],
+            &signature,
+            &public_key
+        ));
+        assert!(!verify_signature(
+            &api,
+            &scheme,
+            &[0x73],
+            &signature,
+            &public_key
+        ));
+    }
+
+    #[test]
+    fn signer_lengths_follow_scheme() {
+        assert!(SignatureScheme::Secp256k1Sha256.accepts_signer(&[2; 33]));
+        assert!(!SignatureScheme::Secp256k1Sha256.accepts_signer(&[0; 20]));
+        assert!(SignatureScheme::Secp256k1Keccak.accepts_signer(&[0; 20]));
+        assert!(SignatureScheme::Ed25519.accepts_signer(&[0; 32]));
+        assert!(!SignatureScheme::Ed25519.accepts_signer(&[2; 33]));
+    }
+
+    #[test]
+    fn scheme_change_keeps_signers_usable() {
+        let mut storage = MockStorage::new();
+        let item: Item<SignatureScheme> = Item::new("signature_scheme");
+        let address = [7u8; 20];
+
+        assert_eq!(
+            set_signature_scheme(
+                &mut storage,
+                &item,
+                false,
+                SignatureScheme::Secp256k1Keccak,
+                [&address[..]]
+            ),
+            Err(SigningError::Unauthorized {})
+        );
+        assert_eq!(
+            set_signature_scheme(
+                &mut storage,
+                &item,
+                true,
+                SignatureScheme::Secp256k1Sha256,
+                [&address[..]]
+            ),
+            Err(SigningError::IncompatibleSigners {})
+        );
+        assert_eq!(
+            get_signature_scheme(&storage, &item),
+            SignatureScheme::Secp256k1Sha256
+        );
+
+        set_signature_scheme(
+            &mut storage,
+            &item,
+            true,
+            SignatureScheme::Secp256k1Keccak,
+            [&address[..]],
+        )
+        .unwrap();
+        assert_eq!(
+            get_signature_scheme(&storage, &item),
+            SignatureScheme::Secp256k1Keccak
+        );
+        assert_eq!(
+            validate_signer(&SignatureScheme::Ed25519, &address),
+            Err(SigningError::InvalidSigner {})
+        );
+    }
+}